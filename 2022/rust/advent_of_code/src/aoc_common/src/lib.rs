@@ -0,0 +1,163 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+
+pub type Answer = Result<String, String>;
+
+pub struct Part {
+    pub description: &'static str,
+    pub solve: fn(&str) -> Answer,
+}
+
+// Entry point of every day, used by its own binary and by the workspace runner
+pub struct Day {
+    pub number: u32,
+    pub title: &'static str,
+    pub input: fn() -> String,
+    pub parts: [Part; 2],
+}
+
+// Reads an input file of the crate calling it, e.g. `read_input!()` or `read_input!("example.txt")`
+#[macro_export]
+macro_rules! read_input {
+    () => {
+        $crate::read_input_file(env!("CARGO_MANIFEST_DIR"), "input.txt")
+    };
+    ($name:expr) => {
+        $crate::read_input_file(env!("CARGO_MANIFEST_DIR"), $name)
+    };
+}
+
+// Path of an input file inside the `input` directory of a crate. Using the crate manifest directory
+// instead of the working directory allows running every day from anywhere.
+pub fn input_path(manifest_dir: &str, name: &str) -> PathBuf {
+    Path::new(manifest_dir).join("input").join(name)
+}
+
+pub fn read_input_file(manifest_dir: &str, name: &str) -> String {
+    fs::read_to_string(input_path(manifest_dir, name)).expect("Cannot open input file")
+}
+
+// Lines of the input, ignoring the blank lines around it. Spaces are kept since they can be
+// meaningful, like in a drawing.
+pub fn lines(input: &str) -> Vec<&str> {
+    let input = trim_line_breaks(input);
+    if input.is_empty() {
+        return Vec::new();
+    }
+
+    input.lines().collect()
+}
+
+// Groups of lines separated by a blank line. Every group is a slice of the input from the start of
+// its first line to the end of its last one, so `\r\n` line breaks are kept inside it.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let offset = |line: &str| line.as_ptr() as usize - input.as_ptr() as usize;
+
+    let mut blocks = Vec::new();
+    let mut block: Option<(&str, &str)> = None;
+    for line in lines(input) {
+        match (line.is_empty(), block) {
+            (true, Some((first, last))) => {
+                blocks.push(&input[offset(first)..offset(last) + last.len()]);
+                block = None;
+            }
+            (true, None) => {}
+            (false, Some((first, _))) => block = Some((first, line)),
+            (false, None) => block = Some((line, line)),
+        }
+    }
+    if let Some((first, last)) = block {
+        blocks.push(&input[offset(first)..offset(last) + last.len()]);
+    }
+
+    blocks
+}
+
+fn trim_line_breaks(input: &str) -> &str {
+    input.trim_matches(|c| c == '\n' || c == '\r')
+}
+
+pub fn parse_lines<T: FromStr>(input: &str) -> Result<Vec<T>, T::Err> {
+    lines(input).into_iter().map(|line| line.trim().parse::<T>()).collect()
+}
+
+pub fn part_name(part: u32) -> &'static str {
+    match part {
+        1 => "one",
+        2 => "two",
+        _ => "unknown",
+    }
+}
+
+pub fn format_answer<T: Display>(part: u32, description: &str, answer: T) -> String {
+    format!("Part {}: {}: {}", part_name(part), description, answer)
+}
+
+pub fn print_answer<T: Display>(part: u32, description: &str, answer: T) {
+    println!("{}", format_answer(part, description, answer));
+}
+
+// Solves both parts of the day printing their answers, exits with an error if any of them fails
+pub fn run_day(day: &Day) {
+    let input = (day.input)();
+    let mut failed = false;
+
+    for (part, solution) in (1..).zip(day.parts.iter()) {
+        match (solution.solve)(&input) {
+            Ok(answer) => print_answer(part, solution.description, answer),
+            Err(error) => {
+                eprintln!("Part {} failed: {}", part_name(part), error);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_path_is_relative_to_the_crate() {
+        assert_eq!(input_path("/foo/day1", "input.txt"), PathBuf::from("/foo/day1/input/input.txt"));
+    }
+
+    #[test]
+    fn input_can_be_split_into_lines() {
+        assert_eq!(lines("\n1000\n2000\r\n3000\n\n"), vec!["1000", "2000", "3000"]);
+        assert_eq!(lines("  [D]\n"), vec!["  [D]"]);
+        assert!(lines("\n\n").is_empty());
+    }
+
+    #[test]
+    fn input_can_be_split_into_blocks() {
+        assert_eq!(blocks("1000\n2000\n\n3000\n"), vec!["1000\n2000", "3000"]);
+        assert_eq!(blocks("    [D]\n 1 \n\nmove"), vec!["    [D]\n 1 ", "move"]);
+        assert!(blocks("").is_empty());
+    }
+
+    #[test]
+    fn blocks_are_split_on_crlf_blank_lines() {
+        assert_eq!(blocks("1000\r\n2000\r\n\r\n3000\r\n"), vec!["1000\r\n2000", "3000"]);
+        assert_eq!(blocks("1\n\n\n2"), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn lines_can_be_parsed() {
+        assert_eq!(parse_lines::<u32>("1\n 2\n3"), Ok(vec![1, 2, 3]));
+        assert!(parse_lines::<u32>("1\nx").is_err());
+    }
+
+    #[test]
+    fn answers_are_formatted_uniformly() {
+        assert_eq!(format_answer(1, "Total score", 15), "Part one: Total score: 15");
+        assert_eq!(format_answer(2, "Stack tops", "MCD"), "Part two: Stack tops: MCD");
+    }
+}
@@ -0,0 +1,173 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+use aoc_common::{read_input, Answer, Day, Part};
+
+pub const DAY: Day = Day {
+    number: 1,
+    title: "Calorie Counting",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Calories carried by the elf with the most calories", solve: part_one },
+        Part { description: "Calories carried by the top three elves", solve: part_two },
+    ],
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Elf {
+    // Position of the elf in the inventory, starting at 0
+    pub index: usize,
+    pub calories: u64,
+}
+
+// Heap key of an elf: the smallest one is the first to be dropped, so with the same calories the
+// elves listed first are kept
+type ElfKey = (u64, Reverse<usize>);
+
+impl Elf {
+    fn key(&self) -> ElfKey {
+        (self.calories, Reverse(self.index))
+    }
+
+    fn from_key((calories, Reverse(index)): ElfKey) -> Self {
+        Elf { index, calories }
+    }
+}
+
+pub fn part_one(input: &str) -> Answer {
+    Ok(total_calories(&top_n_elves(input.as_bytes(), 1)?).to_string())
+}
+
+pub fn part_two(input: &str) -> Answer {
+    Ok(total_calories(&top_n_elves(input.as_bytes(), 3)?).to_string())
+}
+
+pub fn total_calories(elves: &[Elf]) -> u64 {
+    elves.iter().map(|elf| elf.calories).sum()
+}
+
+// Elves carrying the most calories, from the most to the least. The inventory is read line by line
+// keeping only the best `n` elves so far, without storing the whole inventory.
+pub fn top_n_elves<R: BufRead>(reader: R, n: usize) -> Result<Vec<Elf>, String> {
+    let mut heap: BinaryHeap<Reverse<ElfKey>> = BinaryHeap::new();
+    let mut current: Option<Elf> = None;
+    let mut elves = 0;
+
+    let mut push = |elf: Elf| {
+        heap.push(Reverse(elf.key()));
+        if heap.len() > n {
+            heap.pop();
+        }
+    };
+
+    for (number, line) in (1..).zip(reader.lines()) {
+        let line = line.map_err(|error| format!("Cannot read line {}: {}", number, error))?;
+        let line = line.trim();
+
+        // Blank lines separate the elves, more than one in a row doesn't add an empty elf
+        if line.is_empty() {
+            if let Some(elf) = current.take() {
+                push(elf);
+            }
+            continue;
+        }
+
+        let calories = line
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid calories on line {}: {}", number, line))?;
+
+        let elf = current.get_or_insert_with(|| {
+            elves += 1;
+            Elf { index: elves - 1, calories: 0 }
+        });
+        elf.calories = elf
+            .calories
+            .checked_add(calories)
+            .ok_or_else(|| format!("Too many calories for a single elf on line {}", number))?;
+    }
+
+    if let Some(elf) = current {
+        push(elf);
+    }
+
+    // Sorting the min-heap gives the keys from the greatest to the smallest
+    Ok(heap.into_sorted_vec().into_iter().map(|Reverse(key)| Elf::from_key(key)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVENTORY: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    //noinspection SpellCheckingInspection
+    #[test]
+    fn split_full_inventory_into_elf_inventory() {
+        let elves = top_n_elves(INVENTORY.as_bytes(), usize::MAX).unwrap();
+        assert_eq!(elves.len(), 5)
+    }
+
+    #[test]
+    fn get_elf_total_calories() {
+        let elves = top_n_elves("1000\n2000\n3000".as_bytes(), 1).unwrap();
+        assert_eq!(elves, vec![Elf { index: 0, calories: 6000 }])
+    }
+
+    #[test]
+    fn top_elves_are_returned_with_their_index_from_the_most_calories() {
+        let elves = top_n_elves(INVENTORY.as_bytes(), 3).unwrap();
+
+        assert_eq!(
+            elves,
+            vec![
+                Elf { index: 3, calories: 24000 },
+                Elf { index: 2, calories: 11000 },
+                Elf { index: 4, calories: 10000 },
+            ]
+        );
+    }
+
+    #[test]
+    fn elves_carrying_the_same_calories_are_kept_in_inventory_order() {
+        let elves = top_n_elves("100\n\n300\n\n100\n\n200\n100".as_bytes(), 3).unwrap();
+
+        assert_eq!(
+            elves,
+            vec![
+                Elf { index: 1, calories: 300 },
+                Elf { index: 3, calories: 300 },
+                Elf { index: 0, calories: 100 },
+            ]
+        );
+    }
+
+    #[test]
+    fn blank_lines_around_and_between_elves_are_ignored() {
+        let elves = top_n_elves("\n\n100\r\n\r\n\r\n200\n\n".as_bytes(), 5).unwrap();
+
+        assert_eq!(elves, vec![Elf { index: 1, calories: 200 }, Elf { index: 0, calories: 100 }]);
+    }
+
+    #[test]
+    fn no_elves_are_returned_for_an_empty_inventory_or_a_top_of_zero() {
+        assert_eq!(top_n_elves("".as_bytes(), 3), Ok(Vec::new()));
+        assert_eq!(top_n_elves(INVENTORY.as_bytes(), 0), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn malformed_lines_are_reported() {
+        assert_eq!(
+            top_n_elves("1000\n\n20O0\n3000".as_bytes(), 3),
+            Err(String::from("Invalid calories on line 3: 20O0"))
+        );
+        assert!(top_n_elves("-5".as_bytes(), 1).is_err());
+        assert!(part_one("18446744073709551615\n1").is_err());
+    }
+
+    #[test]
+    fn top_calories_are_obtained_from_the_full_inventory() {
+        assert_eq!(part_one(INVENTORY), Ok(String::from("24000")));
+        assert_eq!(part_two(INVENTORY), Ok(String::from("45000")));
+    }
+}
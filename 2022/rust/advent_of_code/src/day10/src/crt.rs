@@ -0,0 +1,241 @@
+use std::str::FromStr;
+
+pub const SPRITE_WIDTH: usize = 3;
+
+// Capital letters of the block font, 4 pixels wide and 6 tall, drawn one after the other with a
+// blank column between them
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+const FONT: [(char, [&str; LETTER_HEIGHT]); 16] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Crt {
+    width: usize,
+    height: usize,
+    sprite_width: usize,
+    // Row after row, from the top left corner
+    pixels: Vec<bool>,
+}
+
+impl Crt {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_sprite_width(width, height, SPRITE_WIDTH)
+    }
+
+    pub fn with_sprite_width(width: usize, height: usize, sprite_width: usize) -> Self {
+        Self { width, height, sprite_width, pixels: vec![false; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_lit(&self, row: usize, column: usize) -> bool {
+        row < self.height && column < self.width && self.pixels[row * self.width + column]
+    }
+
+    // Draws the pixel of the cycle, starting at 1, which is lit if the sprite is over it. The sprite
+    // is centred on `x`, or one pixel to the left of the centre when its width is even. Cycles past
+    // the last pixel are ignored.
+    pub fn draw(&mut self, cycle: u32, x: i32) {
+        let Some(position) = (cycle as usize).checked_sub(1).filter(|&position| position < self.pixels.len()) else {
+            return;
+        };
+
+        let column = (position % self.width) as i64;
+        let left = x as i64 - (self.sprite_width as i64 - 1) / 2;
+        self.pixels[position] = (left..left + self.sprite_width as i64).contains(&column);
+    }
+
+    pub fn render(&self) -> String {
+        (0..self.height)
+            .map(|row| (0..self.width).map(|column| if self.is_lit(row, column) { '#' } else { '.' }).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // Letters drawn with the block font, blank spaces where nothing is drawn
+    pub fn read(&self) -> Result<String, String> {
+        if self.height != LETTER_HEIGHT {
+            return Err(format!("Letters are {} pixels tall but the screen has {} rows", LETTER_HEIGHT, self.height));
+        }
+
+        (0..self.width)
+            .step_by(LETTER_WIDTH + 1)
+            .map(|left| {
+                let glyph = (0..LETTER_HEIGHT)
+                    .map(|row| (left..left + LETTER_WIDTH).map(|column| if self.is_lit(row, column) { '#' } else { '.' }).collect())
+                    .collect::<Vec<String>>();
+
+                if glyph.iter().all(|row| !row.contains('#')) {
+                    return Ok(' ');
+                }
+
+                FONT.iter()
+                    .find(|(_, letter)| *letter == glyph.as_slice())
+                    .map(|&(c, _)| c)
+                    .ok_or_else(|| format!("Unknown letter at column {}:\n{}", left, glyph.join("\n")))
+            })
+            .collect()
+    }
+}
+
+// Rows of `#` and `.` like the ones of `render`, with the default sprite width
+impl FromStr for Crt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s.lines().collect::<Vec<&str>>();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+
+        let mut crt = Crt::new(width, rows.len());
+        for (row, line) in rows.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(format!("Row {} has {} pixels instead of {}", row + 1, line.chars().count(), width));
+            }
+
+            for (column, c) in line.chars().enumerate() {
+                crt.pixels[row * width + column] = match c {
+                    '#' => true,
+                    '.' => false,
+                    _ => return Err(format!("Invalid pixel: {}", c)),
+                };
+            }
+        }
+
+        Ok(crt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Letters of the font side by side, one blank column after each of them
+    fn rendering(letters: &str) -> String {
+        (0..LETTER_HEIGHT)
+            .map(|row| {
+                letters
+                    .chars()
+                    .map(|c| match FONT.iter().find(|&&(letter, _)| letter == c) {
+                        Some((_, glyph)) => format!("{}.", glyph[row]),
+                        None => String::from("....."),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn pixels_are_lit_under_the_sprite() {
+        let mut crt = Crt::new(5, 2);
+        for cycle in 1..=10 {
+            crt.draw(cycle, 2);
+        }
+
+        assert_eq!(crt.render(), ".###.\n.###.");
+    }
+
+    #[test]
+    fn sprite_can_have_any_width() {
+        let draw = |sprite_width, x| {
+            let mut crt = Crt::with_sprite_width(8, 1, sprite_width);
+            for cycle in 1..=8 {
+                crt.draw(cycle, x);
+            }
+            crt.render()
+        };
+
+        assert_eq!(draw(1, 3), "...#....");
+        assert_eq!(draw(2, 3), "...##...");
+        assert_eq!(draw(5, 3), ".#####..");
+        assert_eq!(draw(5, 0), "###.....");
+        assert_eq!(draw(3, -1), "#.......");
+        assert_eq!(draw(3, -2), "........");
+        assert_eq!(draw(3, 8), ".......#");
+        assert_eq!(draw(0, 3), "........");
+    }
+
+    #[test]
+    fn cycles_outside_the_screen_are_ignored() {
+        let mut crt = Crt::new(3, 1);
+        crt.draw(0, 0);
+        crt.draw(4, 0);
+
+        assert_eq!(crt.render(), "...");
+        assert!(!crt.is_lit(1, 0));
+    }
+
+    #[test]
+    fn renderings_can_be_parsed_back() {
+        let rendering = rendering("HELLO");
+        let crt = rendering.parse::<Crt>().unwrap();
+
+        assert_eq!((crt.width(), crt.height()), (25, 6));
+        assert_eq!(crt.render(), rendering);
+        assert_eq!("#.\n#".parse::<Crt>(), Err(String::from("Row 2 has 1 pixels instead of 2")));
+        assert_eq!("#x".parse::<Crt>(), Err(String::from("Invalid pixel: x")));
+    }
+
+    #[test]
+    fn every_letter_of_the_font_is_read() {
+        for letters in ["ABCEFGHJ", "KLOPRSUZ", "ZUSRPOLKJHGFECBA"] {
+            let crt = rendering(letters).parse::<Crt>().unwrap();
+            assert_eq!(crt.read(), Ok(String::from(letters)));
+        }
+    }
+
+    #[test]
+    fn letters_are_read_from_known_renderings() {
+        let crt = "\
+###..####.####.#..#.####.####.#..#..##..
+#..#....#.#....#.#..#....#....#..#.#..#.
+#..#...#..###..##...###..###..####.#..#.
+###...#...#....#.#..#....#....#..#.####.
+#.#..#....#....#.#..#....#....#..#.#..#.
+#..#.####.####.#..#.####.#....#..#.#..#.";
+        assert_eq!(crt.parse::<Crt>().unwrap().read(), Ok(String::from("RZEKEFHA")));
+
+        // The last letter doesn't need the blank column after it
+        let crt = "\
+#..#.###.
+#..#.#..#
+####.#..#
+#..#.###.
+#..#.#...
+#..#.#...";
+        assert_eq!(crt.parse::<Crt>().unwrap().read(), Ok(String::from("HP")));
+
+        assert_eq!(rendering("A B").parse::<Crt>().unwrap().read(), Ok(String::from("A B")));
+    }
+
+    #[test]
+    fn unknown_letters_are_reported() {
+        let crt = rendering("AB").replacen("#", ".", 1).parse::<Crt>().unwrap();
+        assert_eq!(crt.read(), Err(String::from("Unknown letter at column 0:\n..#.\n#..#\n#..#\n####\n#..#\n#..#")));
+
+        assert!(Crt::new(40, 5).read().is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct Op {
+    pub name: &'static str,
+    // Cycles taken to complete, the effect happens at the end of the last one
+    pub cycles: u32,
+    pub operands: usize,
+    // New value of the X register, none if it doesn't fit in it
+    pub execute: fn(i32, &[i32]) -> Option<i32>,
+}
+
+pub const INSTRUCTION_SET: &[Op] = &[
+    Op { name: "noop", cycles: 1, operands: 0, execute: |x, _| Some(x) },
+    Op { name: "addx", cycles: 2, operands: 1, execute: |x, operands| x.checked_add(operands[0]) },
+];
+
+#[derive(Clone, Debug)]
+pub struct Instruction {
+    pub op: &'static Op,
+    pub operands: Vec<i32>,
+}
+
+impl PartialEq for Instruction {
+    fn eq(&self, other: &Self) -> bool {
+        self.op.name == other.op.name && self.operands == other.operands
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let name = words.next().ok_or_else(|| String::from("Empty instruction"))?;
+        let op = INSTRUCTION_SET
+            .iter()
+            .find(|op| op.name == name)
+            .ok_or_else(|| format!("Unknown instruction: {}", name))?;
+
+        let operands = words
+            .map(|word| word.parse::<i32>().map_err(|_| format!("Invalid operand: {}", word)))
+            .collect::<Result<Vec<i32>, String>>()?;
+        if operands.len() != op.operands {
+            return Err(format!("{} takes {} operands but has {}", op.name, op.operands, operands.len()));
+        }
+
+        Ok(Self { op, operands })
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.op.name)?;
+        for operand in &self.operands {
+            write!(f, " {}", operand)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse_program(source: &str) -> Result<Vec<Instruction>, String> {
+    (1..)
+        .zip(source.lines())
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| line.parse().map_err(|error| format!("Line {}: {}", number, error)))
+        .collect()
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Breakpoint {
+    // Stops before running the cycle. It is only checked after running a cycle, so a breakpoint on
+    // the cycle the program is already stopped at doesn't stop it again.
+    Cycle(u32),
+    // Stops when X changes to the value
+    Register(i32),
+}
+
+impl Display for Breakpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Breakpoint::Cycle(cycle) => write!(f, "cycle {}", cycle),
+            Breakpoint::Register(value) => write!(f, "x {}", value),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Stop {
+    Reached,
+    Breakpoint(Breakpoint),
+    Halted,
+}
+
+// Value of X during a cycle and the position of the instruction running in it
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Cycle {
+    pub number: u32,
+    pub instruction: usize,
+    pub x: i32,
+}
+
+pub struct Emulator {
+    program: Vec<Instruction>,
+    // Position of the running instruction
+    pc: usize,
+    // Cycles left for the running instruction to complete
+    remaining: u32,
+    x: i32,
+    // Next cycle to run, starting at 1
+    cycle: u32,
+    breakpoints: Vec<Breakpoint>,
+    trace: Vec<Cycle>,
+}
+
+impl Emulator {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        let remaining = program.first().map_or(0, |instruction| instruction.op.cycles);
+
+        Self { program, pc: 0, remaining, x: 1, cycle: 1, breakpoints: Vec::new(), trace: Vec::new() }
+    }
+
+    // Value of X during the next cycle
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn cycle(&self) -> u32 {
+        self.cycle
+    }
+
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+
+    pub fn current_instruction(&self) -> Option<&Instruction> {
+        self.program.get(self.pc)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    // Runs a single cycle, none if the program already finished. A cycle whose instruction can't be
+    // completed is an error and leaves the emulator as it was before it.
+    pub fn step(&mut self) -> Result<Option<Cycle>, String> {
+        let Some(instruction) = self.program.get(self.pc) else {
+            return Ok(None);
+        };
+
+        let cycle = Cycle { number: self.cycle, instruction: self.pc, x: self.x };
+
+        let remaining = self.remaining.saturating_sub(1);
+        if remaining == 0 {
+            self.x = (instruction.op.execute)(self.x, &instruction.operands)
+                .ok_or_else(|| format!("Cycle {}: {} overflows X = {}", self.cycle, instruction, self.x))?;
+            self.pc += 1;
+            self.remaining = self.program.get(self.pc).map_or(0, |instruction| instruction.op.cycles);
+        } else {
+            self.remaining = remaining;
+        }
+        self.trace.push(cycle);
+        self.cycle += 1;
+
+        Ok(Some(cycle))
+    }
+
+    // Runs the cycles before `cycle`, so that `x` is its value during it, unless a breakpoint stops
+    // it first or the program finishes
+    pub fn run_until(&mut self, cycle: u32) -> Result<Stop, String> {
+        while self.cycle < cycle {
+            let x = self.x;
+            if self.step()?.is_none() {
+                return Ok(Stop::Halted);
+            }
+
+            let hit = self.breakpoints.iter().find(|&&breakpoint| match breakpoint {
+                Breakpoint::Cycle(cycle) => self.cycle == cycle,
+                Breakpoint::Register(value) => self.x == value && x != value,
+            });
+            if let Some(&breakpoint) = hit {
+                return Ok(Stop::Breakpoint(breakpoint));
+            }
+        }
+
+        Ok(Stop::Reached)
+    }
+
+    pub fn run(&mut self) -> Result<Stop, String> {
+        match self.run_until(u32::MAX)? {
+            Stop::Reached => Ok(Stop::Halted),
+            stop => Ok(stop),
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: Breakpoint) -> bool {
+        let count = self.breakpoints.len();
+        self.breakpoints.retain(|&other| other != breakpoint);
+
+        self.breakpoints.len() < count
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    // Every cycle run so far
+    pub fn trace(&self) -> &[Cycle] {
+        &self.trace
+    }
+
+    pub fn format_cycle(&self, cycle: &Cycle) -> String {
+        format!("{:>6}  {:<12}  {:>4}", cycle.number, self.program[cycle.instruction].to_string(), cycle.x)
+    }
+
+    pub fn trace_log(&self) -> String {
+        let mut lines = vec![format!("{:>6}  {:<12}  {:>4}", "Cycle", "Instruction", "X")];
+        lines.extend(self.trace.iter().map(|cycle| self.format_cycle(cycle)));
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_PROGRAM: &str = "noop\naddx 3\naddx -5";
+
+    fn emulator(source: &str) -> Emulator {
+        Emulator::new(parse_program(source).unwrap())
+    }
+
+    #[test]
+    fn instructions_are_parsed_from_the_table() {
+        let instruction = "addx -5".parse::<Instruction>().unwrap();
+        assert_eq!(instruction.op.name, "addx");
+        assert_eq!(instruction.operands, vec![-5]);
+        assert_eq!(instruction.to_string(), "addx -5");
+
+        assert_eq!("noop".parse::<Instruction>().unwrap().to_string(), "noop");
+        assert_eq!("mulx 2".parse::<Instruction>(), Err(String::from("Unknown instruction: mulx")));
+        assert_eq!("addx".parse::<Instruction>(), Err(String::from("addx takes 1 operands but has 0")));
+        assert_eq!("addx y".parse::<Instruction>(), Err(String::from("Invalid operand: y")));
+        assert_eq!(parse_program("noop\n\nnoop 1").err(), Some(String::from("Line 3: noop takes 0 operands but has 1")));
+    }
+
+    #[test]
+    fn add_operation_takes_2_cycles_to_finish() {
+        let mut emulator = emulator("addx 3");
+
+        assert_eq!(emulator.step().unwrap(), Some(Cycle { number: 1, instruction: 0, x: 1 }));
+        assert_eq!(emulator.x(), 1);
+        assert_eq!(emulator.step().unwrap(), Some(Cycle { number: 2, instruction: 0, x: 1 }));
+        assert_eq!(emulator.x(), 4);
+        assert!(emulator.is_halted());
+        assert_eq!(emulator.step().unwrap(), None);
+    }
+
+    #[test]
+    fn small_program_is_run_cycle_by_cycle() {
+        let mut emulator = emulator(SMALL_PROGRAM);
+
+        let cycles = std::iter::from_fn(|| emulator.step().unwrap()).map(|cycle| (cycle.number, cycle.x)).collect::<Vec<(u32, i32)>>();
+        assert_eq!(cycles, vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!(emulator.x(), -1);
+        assert_eq!(emulator.cycle(), 6);
+    }
+
+    #[test]
+    fn program_runs_until_a_cycle() {
+        let mut emulator = emulator(SMALL_PROGRAM);
+
+        assert_eq!(emulator.run_until(4), Ok(Stop::Reached));
+        assert_eq!((emulator.cycle(), emulator.x()), (4, 4));
+        assert_eq!(emulator.current_instruction().map(|instruction| instruction.to_string()), Some(String::from("addx -5")));
+
+        assert_eq!(emulator.run_until(10), Ok(Stop::Halted));
+        assert_eq!((emulator.cycle(), emulator.x()), (6, -1));
+        assert_eq!(emulator.run(), Ok(Stop::Halted));
+    }
+
+    #[test]
+    fn breakpoints_stop_the_program() {
+        let mut emulator = emulator(SMALL_PROGRAM);
+        emulator.add_breakpoint(Breakpoint::Cycle(3));
+        emulator.add_breakpoint(Breakpoint::Register(-1));
+        emulator.add_breakpoint(Breakpoint::Cycle(3));
+        assert_eq!(emulator.breakpoints(), [Breakpoint::Cycle(3), Breakpoint::Register(-1)]);
+
+        assert_eq!(emulator.run(), Ok(Stop::Breakpoint(Breakpoint::Cycle(3))));
+        assert_eq!(emulator.cycle(), 3);
+        assert_eq!(emulator.run(), Ok(Stop::Breakpoint(Breakpoint::Register(-1))));
+        assert_eq!(emulator.cycle(), 6);
+        assert_eq!(emulator.run(), Ok(Stop::Halted));
+
+        assert!(emulator.remove_breakpoint(Breakpoint::Cycle(3)));
+        assert!(!emulator.remove_breakpoint(Breakpoint::Cycle(3)));
+    }
+
+    #[test]
+    fn breakpoint_on_the_current_cycle_does_not_stop_again() {
+        let mut emulator = emulator(SMALL_PROGRAM);
+        emulator.add_breakpoint(Breakpoint::Cycle(1));
+        assert_eq!(emulator.run(), Ok(Stop::Halted));
+
+        let mut emulator = self::emulator(SMALL_PROGRAM);
+        emulator.add_breakpoint(Breakpoint::Cycle(3));
+        assert_eq!(emulator.run(), Ok(Stop::Breakpoint(Breakpoint::Cycle(3))));
+        assert_eq!(emulator.run(), Ok(Stop::Halted));
+    }
+
+    #[test]
+    fn overflowing_the_register_is_an_error() {
+        let mut emulator = emulator("addx 2147483646\nnoop\naddx 1");
+
+        assert_eq!(emulator.run_until(4), Ok(Stop::Reached));
+        assert_eq!(emulator.x(), i32::MAX);
+        assert_eq!(emulator.run(), Err(String::from("Cycle 5: addx 1 overflows X = 2147483647")));
+
+        // The failing cycle isn't run
+        assert_eq!((emulator.cycle(), emulator.x(), emulator.trace().len()), (5, i32::MAX, 4));
+        assert!(emulator.step().is_err());
+    }
+
+    #[test]
+    fn register_breakpoints_only_stop_when_the_value_changes() {
+        let mut emulator = emulator("addx 1\nnoop\nnoop\naddx -1\naddx 1");
+        emulator.add_breakpoint(Breakpoint::Register(2));
+
+        assert_eq!(emulator.run(), Ok(Stop::Breakpoint(Breakpoint::Register(2))));
+        assert_eq!(emulator.cycle(), 3);
+        assert_eq!(emulator.run(), Ok(Stop::Breakpoint(Breakpoint::Register(2))));
+        assert_eq!(emulator.cycle(), 9);
+    }
+
+    #[test]
+    fn every_cycle_is_traced() {
+        let mut emulator = emulator(SMALL_PROGRAM);
+        emulator.run().unwrap();
+
+        assert_eq!(emulator.trace().len(), 5);
+        let expected = " Cycle  Instruction      X
+     1  noop             1
+     2  addx 3           1
+     3  addx 3           1
+     4  addx -5          4
+     5  addx -5          4";
+        assert_eq!(emulator.trace_log(), expected);
+    }
+
+    #[test]
+    fn empty_program_is_halted() {
+        let mut emulator = emulator("");
+
+        assert!(emulator.is_halted());
+        assert_eq!(emulator.step().unwrap(), None);
+        assert_eq!(emulator.run_until(20), Ok(Stop::Halted));
+        assert_eq!(emulator.x(), 1);
+    }
+}
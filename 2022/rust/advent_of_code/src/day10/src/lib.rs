@@ -0,0 +1,231 @@
+#![deny(unused)]
+
+pub mod crt;
+pub mod emulator;
+pub mod repl;
+
+use aoc_common::{read_input, Answer, Day, Part};
+use crate::crt::Crt;
+use crate::emulator::{parse_program, Emulator, Stop};
+
+pub const DAY: Day = Day {
+    number: 10,
+    title: "Cathode-Ray Tube",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Sum of the signal strengths", solve: part_one },
+        Part { description: "Letters rendered by the CRT", solve: part_two },
+    ],
+};
+
+// Cycles during which the signal strength is measured
+const MEASUREMENT_CYCLES: [u32; 6] = [20, 60, 100, 140, 180, 220];
+
+pub fn part_one(input: &str) -> Answer {
+    let mut emulator = Emulator::new(parse_program(input)?);
+
+    let mut signal_strength = 0i64;
+    for cycle in MEASUREMENT_CYCLES {
+        if emulator.run_until(cycle)? != Stop::Reached {
+            break;
+        }
+        signal_strength += cycle as i64 * emulator.x() as i64;
+    }
+
+    Ok(signal_strength.to_string())
+}
+
+pub fn part_two(input: &str) -> Answer {
+    let crt = draw_screen(input)?;
+
+    crt.read().map_err(|error| format!("{}\n{}", error, crt.render()))
+}
+
+pub fn draw_screen(input: &str) -> Result<Crt, String> {
+    let mut emulator = Emulator::new(parse_program(input)?);
+    let mut crt = Crt::new(40, 6);
+
+    while let Some(cycle) = emulator.step()? {
+        crt.draw(cycle.number, cycle.x);
+    }
+
+    Ok(crt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LARGER_PROGRAM: &str = "\
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop";
+
+    #[test]
+    fn both_parts_are_solved_for_the_example() {
+        assert_eq!(part_one(LARGER_PROGRAM), Ok(String::from("13140")));
+
+        let expected = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+        assert_eq!(draw_screen(LARGER_PROGRAM).map(|crt| crt.render()), Ok(String::from(expected)));
+
+        // The example doesn't draw letters
+        let error = part_two(LARGER_PROGRAM).unwrap_err();
+        assert!(error.starts_with("Unknown letter at column 0:\n##..\n###.\n"));
+        assert!(error.ends_with(expected));
+    }
+
+    #[test]
+    fn invalid_programs_are_reported() {
+        assert_eq!(part_one("noop\njmp 3"), Err(String::from("Line 2: Unknown instruction: jmp")));
+        assert_eq!(part_two("addx 2147483647"), Err(String::from("Cycle 2: addx 2147483647 overflows X = 1")));
+    }
+}
@@ -0,0 +1,192 @@
+use std::io::{self, BufRead, Write};
+
+use crate::emulator::{Breakpoint, Emulator, Stop};
+
+const HELP: &str = "\
+step [<cycles>]         Run one or more cycles, printing every one of them
+continue                Run until a breakpoint stops the program or it finishes
+until <cycle>           Run the cycles before the given one
+break cycle <cycle>     Stop before running the cycle
+break x <value>         Stop when X changes to the value
+delete cycle <cycle>    Remove a breakpoint, same arguments as break
+delete x <value>
+breakpoints             List the breakpoints
+print                   Show the next cycle, X and the running instruction
+trace                   Show every cycle run so far
+help                    Show this help
+quit";
+
+// Reads commands until `quit` or the end of the input. Commands can be shortened to their first
+// letter, except `breakpoints`.
+pub fn run<R: BufRead, W: Write>(emulator: &mut Emulator, input: R, out: &mut W) -> io::Result<()> {
+    writeln!(out, "{}", state(emulator))?;
+
+    let mut lines = input.lines();
+    loop {
+        write!(out, "> ")?;
+        out.flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            return Ok(());
+        };
+
+        let words = line.split_whitespace().collect::<Vec<&str>>();
+        match words.as_slice() {
+            [] => {}
+            ["q" | "quit"] => return Ok(()),
+            ["h" | "help"] => writeln!(out, "{}", HELP)?,
+            ["p" | "print"] => writeln!(out, "{}", state(emulator))?,
+            ["s" | "step"] => step(emulator, out, 1)?,
+            ["s" | "step", cycles] => match cycles.parse() {
+                Ok(cycles) => step(emulator, out, cycles)?,
+                Err(_) => writeln!(out, "Invalid number of cycles: {}", cycles)?,
+            },
+            ["c" | "continue"] => {
+                let stop = emulator.run();
+                report(emulator, out, stop)?;
+            }
+            ["u" | "until", cycle] => match cycle.parse() {
+                Ok(cycle) => {
+                    let stop = emulator.run_until(cycle);
+                    report(emulator, out, stop)?;
+                }
+                Err(_) => writeln!(out, "Invalid cycle: {}", cycle)?,
+            },
+            ["b" | "break", kind, value] => match parse_breakpoint(kind, value) {
+                Some(breakpoint) => {
+                    emulator.add_breakpoint(breakpoint);
+                    writeln!(out, "Breakpoint on {}", breakpoint)?;
+                }
+                None => writeln!(out, "Invalid breakpoint: {} {}", kind, value)?,
+            },
+            ["d" | "delete", kind, value] => match parse_breakpoint(kind, value) {
+                Some(breakpoint) if emulator.remove_breakpoint(breakpoint) => {
+                    writeln!(out, "Removed the breakpoint on {}", breakpoint)?
+                }
+                _ => writeln!(out, "No breakpoint on {} {}", kind, value)?,
+            },
+            ["breakpoints"] => {
+                for breakpoint in emulator.breakpoints() {
+                    writeln!(out, "{}", breakpoint)?;
+                }
+            }
+            ["t" | "trace"] => writeln!(out, "{}", emulator.trace_log())?,
+            _ => writeln!(out, "Unknown command: {}, type `help` to see the commands", line.trim())?,
+        }
+    }
+}
+
+fn step<W: Write>(emulator: &mut Emulator, out: &mut W, cycles: u32) -> io::Result<()> {
+    for _ in 0..cycles {
+        match emulator.step() {
+            Ok(Some(cycle)) => writeln!(out, "{}", emulator.format_cycle(&cycle))?,
+            Ok(None) => return writeln!(out, "{}", state(emulator)),
+            Err(error) => return writeln!(out, "Error: {}", error),
+        }
+    }
+
+    Ok(())
+}
+
+fn report<W: Write>(emulator: &Emulator, out: &mut W, stop: Result<Stop, String>) -> io::Result<()> {
+    match stop {
+        Ok(Stop::Breakpoint(breakpoint)) => writeln!(out, "Breakpoint on {}", breakpoint)?,
+        Err(error) => writeln!(out, "Error: {}", error)?,
+        Ok(_) => {}
+    }
+
+    writeln!(out, "{}", state(emulator))
+}
+
+fn parse_breakpoint(kind: &str, value: &str) -> Option<Breakpoint> {
+    match kind {
+        "cycle" => value.parse().ok().map(Breakpoint::Cycle),
+        "x" => value.parse().ok().map(Breakpoint::Register),
+        _ => None,
+    }
+}
+
+fn state(emulator: &Emulator) -> String {
+    match emulator.current_instruction() {
+        Some(instruction) => format!("Cycle {}: X = {}, running {}", emulator.cycle(), emulator.x(), instruction),
+        None => format!("Program finished before cycle {} with X = {}", emulator.cycle(), emulator.x()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::parse_program;
+
+    fn session(commands: &str) -> String {
+        let mut emulator = Emulator::new(parse_program("noop\naddx 3\naddx -5").unwrap());
+
+        let mut out = Vec::new();
+        run(&mut emulator, commands.as_bytes(), &mut out).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn program_is_run_step_by_step() {
+        let expected = "\
+Cycle 1: X = 1, running noop
+>      1  noop             1
+>      2  addx 3           1
+     3  addx 3           1
+> Cycle 4: X = 4, running addx -5
+> ";
+        assert_eq!(session("step\ns 2\nprint\nquit\nstep"), expected);
+    }
+
+    #[test]
+    fn breakpoints_stop_the_program() {
+        let expected = "\
+Cycle 1: X = 1, running noop
+> Breakpoint on x 4
+> Breakpoint on cycle 5
+> x 4
+cycle 5
+> Breakpoint on x 4
+Cycle 4: X = 4, running addx -5
+> Removed the breakpoint on cycle 5
+> Program finished before cycle 6 with X = -1
+> ";
+        assert_eq!(session("break x 4\nb cycle 5\nbreakpoints\ncontinue\nd cycle 5\nc\n"), expected);
+    }
+
+    #[test]
+    fn program_runs_until_a_cycle_and_is_traced() {
+        let out = session("until 3\ntrace\nstep 5");
+
+        assert!(out.contains("> Cycle 3: X = 1, running addx 3\n"));
+        assert!(out.contains(" Cycle  Instruction      X\n     1  noop             1\n     2  addx 3           1\n>"));
+        assert!(out.ends_with("     5  addx -5          4\nProgram finished before cycle 6 with X = -1\n> "));
+    }
+
+    #[test]
+    fn execution_errors_are_reported() {
+        let mut emulator = Emulator::new(parse_program("addx 2147483647").unwrap());
+
+        let mut out = Vec::new();
+        run(&mut emulator, "continue\nstep".as_bytes(), &mut out).unwrap();
+
+        let expected = "\
+Cycle 1: X = 1, running addx 2147483647
+> Error: Cycle 2: addx 2147483647 overflows X = 1
+Cycle 2: X = 1, running addx 2147483647
+> Error: Cycle 2: addx 2147483647 overflows X = 1
+> ";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn invalid_commands_are_reported() {
+        let out = session("jump\nbreak y 3\nstep x\ndelete cycle 3");
+
+        assert!(out.contains("Unknown command: jump, type `help` to see the commands\n"));
+        assert!(out.contains("Invalid breakpoint: y 3\n"));
+        assert!(out.contains("Invalid number of cycles: x\n"));
+        assert!(out.contains("No breakpoint on cycle 3\n"));
+    }
+}
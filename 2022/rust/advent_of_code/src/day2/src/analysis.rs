@@ -0,0 +1,258 @@
+use aoc_common::lines;
+
+use crate::game::{Hand, Outcome};
+use crate::{apply_elf_strategy, evaluate_game, parse_hands, RULES};
+
+// What the second column of the strategy guide means
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Interpretation {
+    Hand,
+    Outcome,
+}
+
+impl Interpretation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Interpretation::Hand => "Hand to play",
+            Interpretation::Outcome => "Outcome wanted",
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Round {
+    pub opponent: Hand,
+    pub response: Hand,
+    pub outcome: Outcome,
+    pub score: u32,
+    // Response with the highest score against the opponent
+    pub optimal: Hand,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Analysis {
+    pub interpretation: Interpretation,
+    pub rounds: Vec<Round>,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub total: u32,
+    // Totals of always playing the best and the worst response to the opponent's hands
+    pub best_total: u32,
+    pub worst_total: u32,
+}
+
+impl Analysis {
+    pub fn optimal_responses(&self) -> Vec<Hand> {
+        self.rounds.iter().map(|round| round.optimal).collect()
+    }
+}
+
+pub fn analyze(input: &str, interpretation: Interpretation) -> Result<Analysis, String> {
+    let mut analysis = Analysis {
+        interpretation,
+        rounds: Vec::new(),
+        wins: 0,
+        draws: 0,
+        losses: 0,
+        total: 0,
+        best_total: 0,
+        worst_total: 0,
+    };
+
+    for line in lines(input) {
+        // Rounds are scored the way the puzzle parts do it, with the wanted outcomes turned into hands
+        let game = match interpretation {
+            Interpretation::Hand => line.to_string(),
+            Interpretation::Outcome => apply_elf_strategy(line)?,
+        };
+        let score = evaluate_game(&game)?;
+        let (opponent, response) = parse_hands(&game)?;
+
+        // With the same score the first hand of the rules is chosen
+        let optimal = RULES
+            .hands()
+            .rev()
+            .max_by_key(|&hand| RULES.score(opponent, hand))
+            .unwrap_or(response);
+        let worst = RULES.hands().map(|hand| RULES.score(opponent, hand)).min().unwrap_or(score);
+
+        let outcome = RULES.outcome(response, opponent);
+        match outcome {
+            Outcome::Win => analysis.wins += 1,
+            Outcome::Draw => analysis.draws += 1,
+            Outcome::Lose => analysis.losses += 1,
+        }
+
+        analysis.total += score;
+        analysis.best_total += RULES.score(opponent, optimal);
+        analysis.worst_total += worst;
+        analysis.rounds.push(Round { opponent, response, outcome, score, optimal });
+    }
+
+    Ok(analysis)
+}
+
+type Figure = (&'static str, fn(&Analysis) -> String);
+
+// One column per analysis, one row per figure
+pub fn summary_table(analyses: &[Analysis]) -> String {
+    let rows: [Figure; 7] = [
+        ("Rounds", |a| a.rounds.len().to_string()),
+        ("Wins", |a| a.wins.to_string()),
+        ("Draws", |a| a.draws.to_string()),
+        ("Losses", |a| a.losses.to_string()),
+        ("Total score", |a| a.total.to_string()),
+        ("Best possible total", |a| a.best_total.to_string()),
+        ("Worst possible total", |a| a.worst_total.to_string()),
+    ];
+
+    let mut header = vec![String::from("Second column")];
+    header.extend(analyses.iter().map(|a| a.interpretation.name().to_string()));
+
+    let mut table = vec![header];
+    for (name, value) in rows {
+        let mut row = vec![name.to_string()];
+        row.extend(analyses.iter().map(value));
+        table.push(row);
+    }
+
+    render_table(&table)
+}
+
+pub fn rounds_table(analysis: &Analysis) -> String {
+    let mut table = vec![["Round", "Opponent", "Response", "Outcome", "Score", "Optimal"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>()];
+
+    for (number, round) in (1..).zip(analysis.rounds.iter()) {
+        table.push(vec![
+            number.to_string(),
+            RULES.name(round.opponent).to_string(),
+            RULES.name(round.response).to_string(),
+            format!("{:?}", round.outcome),
+            round.score.to_string(),
+            RULES.name(round.optimal).to_string(),
+        ]);
+    }
+
+    render_table(&table)
+}
+
+// The first column is left aligned and the rest right aligned, the header is followed by a rule
+fn render_table(table: &[Vec<String>]) -> String {
+    let columns = table.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths = (0..columns)
+        .map(|column| table.iter().filter_map(|row| row.get(column)).map(|cell| cell.chars().count()).max().unwrap_or(0))
+        .collect::<Vec<usize>>();
+
+    let mut lines = Vec::new();
+    for (i, row) in table.iter().enumerate() {
+        let cells = row
+            .iter()
+            .zip(widths.iter())
+            .enumerate()
+            .map(|(column, (cell, &width))| {
+                if column == 0 { format!("{:<width$}", cell) } else { format!("{:>width$}", cell) }
+            })
+            .collect::<Vec<String>>();
+        lines.push(cells.join("  ").trim_end().to_string());
+
+        if i == 0 {
+            lines.push(widths.iter().map(|&width| "-".repeat(width)).collect::<Vec<String>>().join("  "));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUIDE: &str = "A Y\nB X\nC Z";
+
+    fn hands(names: &[&str]) -> Vec<Hand> {
+        names.iter().map(|name| RULES.hand(name).unwrap()).collect()
+    }
+
+    #[test]
+    fn rounds_are_analyzed_with_the_second_column_as_a_hand() {
+        let analysis = analyze(GUIDE, Interpretation::Hand).unwrap();
+
+        let outcomes = analysis.rounds.iter().map(|round| round.outcome).collect::<Vec<Outcome>>();
+        assert_eq!(outcomes, vec![Outcome::Win, Outcome::Lose, Outcome::Draw]);
+        assert_eq!((analysis.wins, analysis.draws, analysis.losses), (1, 1, 1));
+        assert_eq!(analysis.total, 15);
+    }
+
+    #[test]
+    fn rounds_are_analyzed_with_the_second_column_as_an_outcome() {
+        let analysis = analyze(GUIDE, Interpretation::Outcome).unwrap();
+
+        let responses = analysis.rounds.iter().map(|round| round.response).collect::<Vec<Hand>>();
+        assert_eq!(responses, hands(&["Rock", "Rock", "Rock"]));
+        assert_eq!((analysis.wins, analysis.draws, analysis.losses), (1, 1, 1));
+        assert_eq!(analysis.total, 12);
+    }
+
+    #[test]
+    fn best_and_worst_totals_only_depend_on_the_opponent() {
+        let by_hand = analyze(GUIDE, Interpretation::Hand).unwrap();
+        let by_outcome = analyze(GUIDE, Interpretation::Outcome).unwrap();
+
+        // Rock: paper wins (8) or scissors lose (3). Paper: scissors win (9) or rock loses (1).
+        // Scissors: rock wins (7) or paper loses (2).
+        assert_eq!(by_hand.best_total, 24);
+        assert_eq!(by_hand.worst_total, 6);
+        assert_eq!(by_outcome.best_total, by_hand.best_total);
+        assert_eq!(by_outcome.worst_total, by_hand.worst_total);
+    }
+
+    #[test]
+    fn optimal_response_sequence_is_obtained() {
+        let analysis = analyze(GUIDE, Interpretation::Hand).unwrap();
+
+        assert_eq!(analysis.optimal_responses(), hands(&["Paper", "Scissors", "Rock"]));
+    }
+
+    #[test]
+    fn invalid_guides_are_reported() {
+        assert!(analyze("A Y\nB", Interpretation::Hand).is_err());
+        assert!(analyze("A Q", Interpretation::Outcome).is_err());
+    }
+
+    #[test]
+    fn summary_is_printed_as_a_table() {
+        let analyses = [
+            analyze(GUIDE, Interpretation::Hand).unwrap(),
+            analyze(GUIDE, Interpretation::Outcome).unwrap(),
+        ];
+
+        let expected = "\
+Second column         Hand to play  Outcome wanted
+--------------------  ------------  --------------
+Rounds                           3               3
+Wins                             1               1
+Draws                            1               1
+Losses                           1               1
+Total score                     15              12
+Best possible total             24              24
+Worst possible total             6               6";
+
+        assert_eq!(summary_table(&analyses), expected);
+    }
+
+    #[test]
+    fn rounds_are_printed_as_a_table() {
+        let analysis = analyze("A Y", Interpretation::Hand).unwrap();
+
+        let expected = "\
+Round  Opponent  Response  Outcome  Score  Optimal
+-----  --------  --------  -------  -----  -------
+1          Rock     Paper      Win      8    Paper";
+
+        assert_eq!(rounds_table(&analysis), expected);
+    }
+}
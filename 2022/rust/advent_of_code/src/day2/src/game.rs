@@ -1,54 +1,133 @@
-use std::cmp::Ordering;
-use std::cmp::Ordering::{Equal, Greater, Less};
-
-#[derive(PartialEq, Eq, Debug, Clone)]
-pub enum Hand {
-    Rock,
-    Paper,
-    Scissors,
-    Unknown(String),
+// A hand game is defined by its moves and which move beats which. A round is won by playing a move
+// that beats the opponent's one, lost the other way around and a draw in any other case.
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
 }
 
-impl From<char> for Hand {
-    fn from(chr: char) -> Self {
-        match chr {
-            'A' | 'X' => Hand::Rock,
-            'B' | 'Y' => Hand::Paper,
-            'C' | 'Z' => Hand::Scissors,
-            _ => Hand::Unknown(format!("{}", chr))
+impl Outcome {
+    pub fn score(&self) -> u32 {
+        match self {
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
         }
     }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self {
-            Hand::Rock => {
-                match other {
-                    Hand::Rock => Some(Equal),
-                    Hand::Paper => Some(Less),
-                    Hand::Scissors => Some(Greater),
-                    _ => None
-                }
+// A move of a game, identified by its position in the moves of the rules it was obtained from
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub struct Hand(usize);
+
+impl Hand {
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+pub struct Rules {
+    pub moves: &'static [&'static str],
+    // Pairs of moves where the first one beats the second one
+    pub beats: &'static [(&'static str, &'static str)],
+}
+
+pub const ROCK_PAPER_SCISSORS: Rules = Rules {
+    moves: &["Rock", "Paper", "Scissors"],
+    beats: &[
+        ("Rock", "Scissors"),
+        ("Paper", "Rock"),
+        ("Scissors", "Paper"),
+    ],
+};
+
+pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: Rules = Rules {
+    moves: &["Rock", "Paper", "Scissors", "Lizard", "Spock"],
+    beats: &[
+        ("Scissors", "Paper"),
+        ("Paper", "Rock"),
+        ("Rock", "Lizard"),
+        ("Lizard", "Spock"),
+        ("Spock", "Scissors"),
+        ("Scissors", "Lizard"),
+        ("Lizard", "Paper"),
+        ("Paper", "Spock"),
+        ("Spock", "Rock"),
+        ("Rock", "Scissors"),
+    ],
+};
+
+impl Rules {
+    pub fn hand(&self, name: &str) -> Option<Hand> {
+        self.moves.iter().position(|&m| m == name).map(Hand)
+    }
+
+    pub fn hand_at(&self, index: usize) -> Option<Hand> {
+        (index < self.moves.len()).then_some(Hand(index))
+    }
+
+    pub fn hands(&self) -> impl DoubleEndedIterator<Item = Hand> {
+        (0..self.moves.len()).map(Hand)
+    }
+
+    pub fn name(&self, hand: Hand) -> &'static str {
+        self.moves[hand.0]
+    }
+
+    // Every move scores its position in the list of moves, starting at 1
+    pub fn hand_score(&self, hand: Hand) -> u32 {
+        hand.0 as u32 + 1
+    }
+
+    pub fn beats(&self, hand: Hand, other: Hand) -> bool {
+        let (hand, other) = (self.name(hand), self.name(other));
+
+        self.beats.iter().any(|&(winner, loser)| winner == hand && loser == other)
+    }
+
+    pub fn outcome(&self, hand: Hand, opponent: Hand) -> Outcome {
+        if self.beats(hand, opponent) {
+            Outcome::Win
+        } else if self.beats(opponent, hand) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    // Score of a round for the player of `hand`
+    pub fn score(&self, opponent: Hand, hand: Hand) -> u32 {
+        self.hand_score(hand) + self.outcome(hand, opponent).score()
+    }
+
+    // Move to play against the opponent to get the outcome. If several moves do it, the first one
+    // in the list of moves is chosen.
+    pub fn resolve(&self, opponent: Hand, outcome: Outcome) -> Option<Hand> {
+        self.hands().find(|&hand| self.outcome(hand, opponent) == outcome)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, name) in self.moves.iter().enumerate() {
+            if self.moves[..i].contains(name) {
+                return Err(format!("Duplicated move: {}", name));
             }
-            Hand::Paper => {
-                match other {
-                    Hand::Rock => Some(Greater),
-                    Hand::Paper => Some(Equal),
-                    Hand::Scissors => Some(Less),
-                    _ => None
-                }
+        }
+
+        for &(winner, loser) in self.beats {
+            if self.hand(winner).is_none() || self.hand(loser).is_none() {
+                return Err(format!("Unknown move in rule: {} beats {}", winner, loser));
             }
-            Hand::Scissors => {
-                match other {
-                    Hand::Rock => Some(Less),
-                    Hand::Paper => Some(Greater),
-                    Hand::Scissors => Some(Equal),
-                    _ => None
-                }
+            if winner == loser {
+                return Err(format!("A move cannot beat itself: {}", winner));
+            }
+            if self.beats.contains(&(loser, winner)) {
+                return Err(format!("Contradictory rules: {} and {} beat each other", winner, loser));
             }
-            _ => None
         }
+
+        Ok(())
     }
 }
 
@@ -56,39 +135,108 @@ impl PartialOrd for Hand {
 mod tests {
     use super::*;
 
+    fn hand(rules: &Rules, name: &str) -> Hand {
+        rules.hand(name).unwrap()
+    }
+
     #[test]
     fn hand_is_identified() {
-        let hand = Hand::from('A');
-        assert_eq!(hand, Hand::Rock);
+        let rules = ROCK_PAPER_SCISSORS;
 
-        let hand = Hand::from('B');
-        assert_eq!(hand, Hand::Paper);
+        assert_eq!(rules.hand("Rock"), rules.hand_at(0));
+        assert_eq!(rules.hand("Paper"), rules.hand_at(1));
+        assert_eq!(rules.hand("Scissors"), rules.hand_at(2));
 
-        let hand = Hand::from('C');
-        assert_eq!(hand, Hand::Scissors);
+        assert_eq!(rules.hand("Lizard"), None);
+        assert_eq!(rules.hand_at(3), None);
+    }
 
-        let hand = Hand::from('X');
-        assert_eq!(hand, Hand::Rock);
+    #[test]
+    fn game_rules_are_applied() {
+        let rules = ROCK_PAPER_SCISSORS;
+        let (rock, paper, scissors) = (hand(&rules, "Rock"), hand(&rules, "Paper"), hand(&rules, "Scissors"));
 
-        let hand = Hand::from('Y');
-        assert_eq!(hand, Hand::Paper);
+        assert_eq!(rules.outcome(rock, rock), Outcome::Draw);
+        assert_eq!(rules.outcome(rock, paper), Outcome::Lose);
+        assert_eq!(rules.outcome(rock, scissors), Outcome::Win);
 
-        let hand = Hand::from('Z');
-        assert_eq!(hand, Hand::Scissors);
+        assert_eq!(rules.outcome(paper, paper), Outcome::Draw);
+        assert_eq!(rules.outcome(paper, scissors), Outcome::Lose);
+        assert_eq!(rules.outcome(paper, rock), Outcome::Win);
+
+        assert_eq!(rules.outcome(scissors, scissors), Outcome::Draw);
+        assert_eq!(rules.outcome(scissors, rock), Outcome::Lose);
+        assert_eq!(rules.outcome(scissors, paper), Outcome::Win);
     }
 
     #[test]
-    fn game_rules_are_applied() {
-        assert_eq!(Hand::Rock, Hand::Rock);
-        assert!(Hand::Rock < Hand::Paper);
-        assert!(Hand::Rock > Hand::Scissors);
+    fn round_score_adds_the_hand_and_the_outcome_scores() {
+        let rules = ROCK_PAPER_SCISSORS;
+
+        assert_eq!(rules.score(hand(&rules, "Rock"), hand(&rules, "Paper")), 8);
+        assert_eq!(rules.score(hand(&rules, "Paper"), hand(&rules, "Rock")), 1);
+        assert_eq!(rules.score(hand(&rules, "Scissors"), hand(&rules, "Scissors")), 6);
+    }
+
+    #[test]
+    fn lizard_and_spock_rules_are_applied() {
+        let rules = ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+
+        assert_eq!(rules.outcome(hand(&rules, "Spock"), hand(&rules, "Rock")), Outcome::Win);
+        assert_eq!(rules.outcome(hand(&rules, "Lizard"), hand(&rules, "Spock")), Outcome::Win);
+        assert_eq!(rules.outcome(hand(&rules, "Lizard"), hand(&rules, "Scissors")), Outcome::Lose);
+        assert_eq!(rules.outcome(hand(&rules, "Paper"), hand(&rules, "Paper")), Outcome::Draw);
+        assert_eq!(rules.score(hand(&rules, "Rock"), hand(&rules, "Spock")), 11);
+    }
+
+    #[test]
+    fn every_hand_wins_and_loses_against_the_same_number_of_hands() {
+        for rules in [ROCK_PAPER_SCISSORS, ROCK_PAPER_SCISSORS_LIZARD_SPOCK] {
+            for hand in rules.hands() {
+                let wins = rules.hands().filter(|&other| rules.outcome(hand, other) == Outcome::Win).count();
+                let losses = rules.hands().filter(|&other| rules.outcome(hand, other) == Outcome::Lose).count();
+
+                assert_eq!(wins, (rules.moves.len() - 1) / 2);
+                assert_eq!(losses, wins);
+            }
+        }
+    }
+
+    #[test]
+    fn desired_outcome_is_resolved_to_a_hand() {
+        for rules in [ROCK_PAPER_SCISSORS, ROCK_PAPER_SCISSORS_LIZARD_SPOCK] {
+            for opponent in rules.hands() {
+                for outcome in [Outcome::Lose, Outcome::Draw, Outcome::Win] {
+                    let hand = rules.resolve(opponent, outcome).unwrap();
+
+                    assert_eq!(rules.outcome(hand, opponent), outcome);
+                }
+            }
+        }
 
-        assert_eq!(Hand::Paper, Hand::Paper);
-        assert!(Hand::Paper < Hand::Scissors);
-        assert!(Hand::Paper > Hand::Rock);
+        let rules = ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+        assert_eq!(rules.resolve(hand(&rules, "Rock"), Outcome::Win), rules.hand("Paper"));
+        assert_eq!(rules.resolve(hand(&rules, "Rock"), Outcome::Draw), rules.hand("Rock"));
+    }
 
-        assert_eq!(Hand::Scissors, Hand::Scissors);
-        assert!(Hand::Scissors < Hand::Rock);
-        assert!(Hand::Scissors > Hand::Paper);
+    #[test]
+    fn shipped_rules_are_valid() {
+        assert_eq!(ROCK_PAPER_SCISSORS.validate(), Ok(()));
+        assert_eq!(ROCK_PAPER_SCISSORS_LIZARD_SPOCK.validate(), Ok(()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn invalid_rules_are_detected() {
+        let unknown = Rules { moves: &["Rock", "Paper"], beats: &[("Paper", "Stone")] };
+        assert!(unknown.validate().is_err());
+
+        let itself = Rules { moves: &["Rock"], beats: &[("Rock", "Rock")] };
+        assert!(itself.validate().is_err());
+
+        let contradictory = Rules { moves: &["Rock", "Paper"], beats: &[("Paper", "Rock"), ("Rock", "Paper")] };
+        assert!(contradictory.validate().is_err());
+
+        let duplicated = Rules { moves: &["Rock", "Rock"], beats: &[] };
+        assert!(duplicated.validate().is_err());
+    }
+}
@@ -0,0 +1,191 @@
+pub mod analysis;
+pub mod game;
+
+use aoc_common::{read_input, Answer, Day, Part};
+use analysis::{analyze, Interpretation};
+use game::*;
+
+// The strategy guide is written for a plain game of rock paper scissors
+const RULES: Rules = ROCK_PAPER_SCISSORS;
+
+// The opponent uses A, B, C for its hands and the response X, Y, Z
+const OPPONENT_LETTERS: &str = "ABC";
+const RESPONSE_LETTERS: &str = "XYZ";
+
+pub const DAY: Day = Day {
+    number: 2,
+    title: "Rock Paper Scissors",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Total score", solve: part_one },
+        Part { description: "Total score when applying the elf's strategy", solve: part_two },
+    ],
+};
+
+pub fn part_one(input: &str) -> Answer {
+    Ok(analyze(input, Interpretation::Hand)?.total.to_string())
+}
+
+pub fn part_two(input: &str) -> Answer {
+    Ok(analyze(input, Interpretation::Outcome)?.total.to_string())
+}
+
+pub fn evaluate_game(game: &str) -> Result<u32, String> {
+    let (opponent, response) = parse_hands(game)?;
+
+    Ok(RULES.score(opponent, response))
+}
+
+// The second column is the outcome the elf wants instead of the hand to play, the hand that gets it
+// is written back with the response letters, so that the game can be evaluated
+pub fn apply_elf_strategy(game: &str) -> Result<String, String> {
+    let (opponent, response) = resolve_elf_strategy(game)?;
+
+    Ok(format!("{} {}", letter_from_hand(opponent), response_letter_from_hand(response)))
+}
+
+// Hands of the opponent and of the response when the second column is the hand to play
+fn parse_hands(game: &str) -> Result<(Hand, Hand), String> {
+    let (opponent, response) = parse_game(game)?;
+
+    Ok((hand_from_letter(opponent, OPPONENT_LETTERS)?, hand_from_letter(response, RESPONSE_LETTERS)?))
+}
+
+// Hands of the opponent and of the response when the second column is the outcome wanted
+fn resolve_elf_strategy(game: &str) -> Result<(Hand, Hand), String> {
+    let (opponent, outcome) = parse_game(game)?;
+    let opponent = hand_from_letter(opponent, OPPONENT_LETTERS)?;
+
+    let outcome = match outcome {
+        'X' => Outcome::Lose,
+        'Y' => Outcome::Draw,
+        'Z' => Outcome::Win,
+        _ => return Err(format!("Invalid outcome: {}", outcome)),
+    };
+
+    let response = RULES
+        .resolve(opponent, outcome)
+        .ok_or_else(|| format!("No hand gets a {:?} against {}", outcome, letter_from_hand(opponent)))?;
+
+    Ok((opponent, response))
+}
+
+fn parse_game(game: &str) -> Result<(char, char), String> {
+    let letters = game
+        .trim()
+        .split(' ')
+        .map(|letter| {
+            let mut chars = letter.chars();
+            match (chars.next(), chars.next()) {
+                (Some(chr), None) => Some(chr),
+                _ => None,
+            }
+        })
+        .collect::<Option<Vec<char>>>();
+
+    match letters.as_deref() {
+        Some(&[opponent, response]) => Ok((opponent, response)),
+        _ => Err(format!("Invalid game: {}", game)),
+    }
+}
+
+// Each column has its own letters, the position of the letter is the one of the hand in the rules
+fn hand_from_letter(letter: char, letters: &str) -> Result<Hand, String> {
+    letters
+        .find(letter)
+        .and_then(|index| RULES.hand_at(index))
+        .ok_or_else(|| format!("Invalid hand: {}", letter))
+}
+
+fn letter_from_hand(hand: Hand) -> char {
+    OPPONENT_LETTERS.as_bytes()[hand.index()] as char
+}
+
+fn response_letter_from_hand(hand: Hand) -> char {
+    RESPONSE_LETTERS.as_bytes()[hand.index()] as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_score_is_calculated() {
+        let score = evaluate_game("A Y").unwrap();
+        assert_eq!(score, 8);
+
+        let score = evaluate_game("B X").unwrap();
+        assert_eq!(score, 1);
+
+        let score = evaluate_game("C Z").unwrap();
+        assert_eq!(score, 6);
+    }
+
+    #[test]
+    fn elf_strategy_is_applied() {
+        let updated_game = apply_elf_strategy("A X").unwrap();
+        assert_eq!(updated_game, "A Z");
+
+        let updated_game = apply_elf_strategy("A Y").unwrap();
+        assert_eq!(updated_game, "A X");
+
+        let updated_game = apply_elf_strategy("A Z").unwrap();
+        assert_eq!(updated_game, "A Y");
+
+        let updated_game = apply_elf_strategy("B X").unwrap();
+        assert_eq!(updated_game, "B X");
+
+        let updated_game = apply_elf_strategy("B Y").unwrap();
+        assert_eq!(updated_game, "B Y");
+
+        let updated_game = apply_elf_strategy("B Z").unwrap();
+        assert_eq!(updated_game, "B Z");
+
+        let updated_game = apply_elf_strategy("C X").unwrap();
+        assert_eq!(updated_game, "C Y");
+
+        let updated_game = apply_elf_strategy("C Y").unwrap();
+        assert_eq!(updated_game, "C Z");
+
+        let updated_game = apply_elf_strategy("C Z").unwrap();
+        assert_eq!(updated_game, "C X");
+    }
+
+    #[test]
+    fn games_with_the_elf_strategy_applied_can_be_evaluated() {
+        let input = "A Y\nB X\nC Z";
+        let scores = input
+            .lines()
+            .map(|game| evaluate_game(&apply_elf_strategy(game)?))
+            .collect::<Result<Vec<u32>, String>>();
+
+        assert_eq!(scores, Ok(vec![4, 1, 7]));
+        assert_eq!(scores.map(|scores| scores.iter().sum::<u32>().to_string()), part_two(input));
+    }
+
+    #[test]
+    fn total_score_of_the_strategy_guide_is_calculated() {
+        let input = "A Y\nB X\nC Z";
+
+        assert_eq!(part_one(input), Ok(String::from("15")));
+        assert_eq!(part_two(input), Ok(String::from("12")));
+    }
+
+    #[test]
+    fn invalid_games_are_reported() {
+        assert!(evaluate_game("A").is_err());
+        assert!(evaluate_game("A Y Z").is_err());
+        assert!(evaluate_game("AB Y").is_err());
+        assert!(evaluate_game("D Y").is_err());
+        assert!(apply_elf_strategy("A C").is_err());
+        assert_eq!(part_one("A Y\nB W"), Err(String::from("Invalid hand: W")));
+    }
+
+    #[test]
+    fn each_column_only_accepts_its_own_letters() {
+        assert_eq!(evaluate_game("X A"), Err(String::from("Invalid hand: X")));
+        assert_eq!(evaluate_game("A B"), Err(String::from("Invalid hand: B")));
+        assert_eq!(apply_elf_strategy("Y Z"), Err(String::from("Invalid hand: Y")));
+        assert_eq!(part_two("A Y\nZ X"), Err(String::from("Invalid hand: Z")));
+    }
+}
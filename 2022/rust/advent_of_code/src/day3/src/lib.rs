@@ -0,0 +1,281 @@
+use std::fmt::{Display, Formatter};
+use std::ops::BitAnd;
+
+use aoc_common::{lines, read_input, Answer, Day, Part};
+
+pub const DAY: Day = Day {
+    number: 3,
+    title: "Rucksack Reorganization",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Sum of the priorities of the shared items", solve: part_one },
+        Part { description: "Sum of the priorities of the group badges", solve: part_two },
+    ],
+};
+
+const ELVES_PER_GROUP: usize = 3;
+
+// Set of the 52 item types, the bit `priority - 1` is set when the item is in the set
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const ALL: ItemSet = ItemSet((1 << 52) - 1);
+
+    pub fn insert(&mut self, item: char) -> Result<(), String> {
+        let priority = get_item_priority(item).ok_or_else(|| format!("Invalid item: {}", item))?;
+        self.0 |= 1 << (priority - 1);
+
+        Ok(())
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        get_item_priority(item).is_some_and(|priority| self.0 & (1 << (priority - 1)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn intersection(&self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    // Items shared by all the sets, there are no shared items without sets
+    pub fn intersection_of<I: IntoIterator<Item = ItemSet>>(sets: I) -> ItemSet {
+        let mut sets = sets.into_iter();
+        match sets.next() {
+            Some(first) => sets.fold(first, |shared, set| shared & set),
+            None => ItemSet::default(),
+        }
+    }
+
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |priority| self.0 & (1 << (priority - 1)) != 0)
+    }
+
+    pub fn items(self) -> impl Iterator<Item = char> {
+        self.priorities().map(get_item)
+    }
+
+    pub fn total_priority(&self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        self.intersection(other)
+    }
+}
+
+impl TryFrom<&str> for ItemSet {
+    type Error = String;
+
+    fn try_from(items: &str) -> Result<Self, Self::Error> {
+        let mut set = ItemSet::default();
+        for item in items.chars() {
+            set.insert(item)?;
+        }
+
+        Ok(set)
+    }
+}
+
+impl Display for ItemSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.items().collect::<String>())
+    }
+}
+
+struct Rucksack<'a> {
+    pub compartments: [&'a str; 2],
+}
+
+impl<'a> Rucksack<'a> {
+    pub fn new(items: &'a str) -> Result<Self, String> {
+        if !items.len().is_multiple_of(2) || !items.is_ascii() {
+            return Err(format!("Items can't be split into two compartments: {}", items));
+        }
+
+        let (c0_items, c1_items) = items.split_at(items.len() / 2);
+
+        Ok(Self { compartments: [c0_items, c1_items] })
+    }
+
+    pub fn get_shared_items(&self) -> Result<ItemSet, String> {
+        shared_items(&self.compartments)
+    }
+}
+
+// Items found in every one of the lists, used both for the compartments of a rucksack and for the
+// rucksacks of a group
+pub fn shared_items(lists: &[&str]) -> Result<ItemSet, String> {
+    let sets = lists.iter().map(|&items| ItemSet::try_from(items)).collect::<Result<Vec<ItemSet>, String>>()?;
+
+    Ok(ItemSet::intersection_of(sets))
+}
+
+// Badge of every group of elves, the only item carried by all of them
+pub fn badges(input: &str, group_size: usize) -> Result<Vec<char>, String> {
+    let lines = lines(input);
+    if group_size == 0 || !lines.len().is_multiple_of(group_size) {
+        return Err(format!("{} rucksacks can't be split into groups of {}", lines.len(), group_size));
+    }
+
+    lines
+        .chunks(group_size)
+        .map(|group| {
+            let shared = shared_items(group)?;
+            match (shared.len(), shared.items().next()) {
+                (1, Some(badge)) => Ok(badge),
+                _ => Err(format!("Group must share a single item, found \"{}\": {}", shared, group.join(", "))),
+            }
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Answer {
+    let mut total_priority = 0;
+    for line in lines(input) {
+        total_priority += Rucksack::new(line)?.get_shared_items()?.total_priority();
+    }
+
+    Ok(total_priority.to_string())
+}
+
+pub fn part_two(input: &str) -> Answer {
+    let badges = badges(input, ELVES_PER_GROUP)?;
+
+    Ok(badges.into_iter().filter_map(get_item_priority).sum::<u32>().to_string())
+}
+
+pub fn get_item_priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some((item as u32) - ('a' as u32) + 1),
+        'A'..='Z' => Some((item as u32) - ('A' as u32) + 27),
+        _ => None,
+    }
+}
+
+fn get_item(priority: u32) -> char {
+    if priority <= 26 {
+        char::from(b'a' + priority as u8 - 1)
+    } else {
+        char::from(b'A' + priority as u8 - 27)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rucksack_is_created_correctly() {
+        let rucksak = Rucksack::new("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+
+        assert_eq!(rucksak.compartments[0], "vJrwpWtwJgWr");
+        assert_eq!(rucksak.compartments[1], "hcsFMMfFFhFp");
+
+        let rucksak = Rucksack::new("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL").unwrap();
+
+        assert_eq!(rucksak.compartments[0], "jqHRNqRjqzjGDLGL");
+        assert_eq!(rucksak.compartments[1], "rsFMfFZSrLrFZsSL");
+
+        assert!(Rucksack::new("abc").is_err());
+    }
+
+    #[test]
+    fn is_possible_to_get_shared_items() {
+        let rucksak = Rucksack::new("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+
+        let shared_items = rucksak.get_shared_items().unwrap();
+        let expected_shared_items = vec!['p'];
+
+        assert_eq!(shared_items.items().collect::<Vec<char>>(), expected_shared_items);
+
+        let rucksak = Rucksack::new("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL").unwrap();
+
+        let shared_items = rucksak.get_shared_items().unwrap();
+        let expected_shared_items = vec!['L'];
+
+        assert_eq!(shared_items.items().collect::<Vec<char>>(), expected_shared_items);
+    }
+
+    #[test]
+    fn item_priority_can_be_retrieved() {
+        assert_eq!(get_item_priority('a'), Some(1));
+        assert_eq!(get_item_priority('g'), Some(7));
+        assert_eq!(get_item_priority('n'), Some(14));
+        assert_eq!(get_item_priority('z'), Some(26));
+        assert_eq!(get_item_priority('A'), Some(27));
+        assert_eq!(get_item_priority('L'), Some(38));
+        assert_eq!(get_item_priority('O'), Some(41));
+        assert_eq!(get_item_priority('Z'), Some(52));
+
+        assert_eq!(get_item_priority('1'), None);
+        assert_eq!(get_item_priority('é'), None);
+    }
+
+    #[test]
+    fn item_set_holds_every_item_once() {
+        let set = ItemSet::try_from("zaAZaz").unwrap();
+
+        assert_eq!(set.len(), 4);
+        assert!(set.contains('a') && set.contains('z') && set.contains('A') && set.contains('Z'));
+        assert!(!set.contains('b') && !set.contains('?'));
+        assert_eq!(set.to_string(), "azAZ");
+        assert_eq!(set.total_priority(), 1 + 26 + 27 + 52);
+
+        assert!(ItemSet::try_from("ab c").is_err());
+    }
+
+    #[test]
+    fn all_the_items_fit_in_the_set() {
+        let items = ('a'..='z').chain('A'..='Z').collect::<String>();
+        let set = ItemSet::try_from(items.as_str()).unwrap();
+
+        assert_eq!(set, ItemSet::ALL);
+        assert_eq!(set.len(), 52);
+        assert_eq!(set.to_string(), items);
+    }
+
+    #[test]
+    fn items_shared_by_any_number_of_sets_are_obtained() {
+        let sets = ["abcX", "bcdX", "cXef", "XcZ"].map(|items| ItemSet::try_from(items).unwrap());
+
+        assert_eq!(ItemSet::intersection_of(sets).to_string(), "cX");
+        assert_eq!(ItemSet::intersection_of(sets[..1].iter().copied()), sets[0]);
+        assert!(ItemSet::intersection_of([]).is_empty());
+    }
+
+    #[test]
+    fn badges_are_found_for_any_group_size() {
+        let input = "abcA\nAdef\ngAhi\nBjkl\nmBno\npqBr";
+
+        assert_eq!(badges(input, 3), Ok(vec!['A', 'B']));
+        assert_eq!(badges("xa\nyb\nab", 1), Err(String::from("Group must share a single item, found \"ax\": xa")));
+        assert_eq!(badges("aB\nBc\ndBe\nfB\nBg\nhiB", 6), Ok(vec!['B']));
+        assert!(badges(input, 4).is_err());
+        assert!(badges(input, 0).is_err());
+    }
+
+    #[test]
+    fn total_priorities_are_calculated() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+        assert_eq!(part_one(input), Ok(String::from("157")));
+        assert_eq!(part_two(input), Ok(String::from("70")));
+    }
+}
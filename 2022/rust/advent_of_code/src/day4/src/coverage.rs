@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+
+use crate::interval::Interval;
+
+// Run of consecutive sections assigned to the same number of elves
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Segment {
+    pub sections: Interval,
+    pub elves: usize,
+}
+
+// How many elves are assigned to every section, from the first assigned section to the last one.
+// Elves are numbered in the order of the assignments, so the elves of the pair `i` are `2i` and
+// `2i + 1`.
+pub struct CoverageMap {
+    assignments: Vec<Interval>,
+    pub segments: Vec<Segment>,
+}
+
+impl CoverageMap {
+    pub fn new(assignments: &[Interval]) -> Self {
+        // Number of elves starting and stopping at every section. The section after the end of an
+        // assignment can be 2^64, so u128 is used for the positions.
+        let mut changes: BTreeMap<u128, i64> = BTreeMap::new();
+        for assignment in assignments {
+            *changes.entry(assignment.start as u128).or_default() += 1;
+            *changes.entry(assignment.end as u128 + 1).or_default() -= 1;
+        }
+
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut elves = 0i64;
+        let positions = changes.into_iter().collect::<Vec<(u128, i64)>>();
+        for window in positions.windows(2) {
+            let ((start, change), (next, _)) = (window[0], window[1]);
+            elves += change;
+
+            let sections = Interval::new(start as u64, (next - 1) as u64);
+            match segments.last_mut() {
+                Some(last) if last.elves == elves as usize => last.sections.end = sections.end,
+                _ => segments.push(Segment { sections, elves: elves as usize }),
+            }
+        }
+
+        Self { assignments: assignments.to_vec(), segments }
+    }
+
+    pub fn from_pairs(pairs: &[(Interval, Interval)]) -> Self {
+        let assignments = pairs.iter().flat_map(|&(first, second)| [first, second]).collect::<Vec<Interval>>();
+
+        Self::new(&assignments)
+    }
+
+    // From the first assigned section to the last one
+    pub fn span(&self) -> Option<Interval> {
+        let first = self.segments.first()?;
+        let last = self.segments.last()?;
+
+        Some(Interval::new(first.sections.start, last.sections.end))
+    }
+
+    pub fn uncovered(&self) -> Vec<Interval> {
+        self.sections_where(|elves| elves == 0)
+    }
+
+    pub fn covered_by_many(&self) -> Vec<Interval> {
+        self.sections_where(|elves| elves > 1)
+    }
+
+    fn sections_where(&self, condition: fn(usize) -> bool) -> Vec<Interval> {
+        Interval::merge(self.segments.iter().filter(|segment| condition(segment.elves)).map(|segment| segment.sections))
+    }
+
+    // Fewest elves whose assignments cover every assigned section, which is the whole span when
+    // there are no uncovered sections. Each step takes, among the elves starting at the first
+    // section not covered yet, the one that reaches further.
+    pub fn minimal_cover(&self) -> Vec<usize> {
+        let mut elves = (0..self.assignments.len()).collect::<Vec<usize>>();
+        elves.sort_by_key(|&elf| self.assignments[elf].start);
+
+        let mut cover = Vec::new();
+        // First section not covered yet, u128 since it can be past u64::MAX
+        let mut next = 0u128;
+        let mut i = 0;
+        while i < elves.len() {
+            // Skip the gap up to the next assignment
+            next = next.max(self.assignments[elves[i]].start as u128);
+
+            let mut best: Option<usize> = None;
+            while i < elves.len() && self.assignments[elves[i]].start as u128 <= next {
+                let elf = elves[i];
+                if best.is_none_or(|best| self.assignments[elf].end > self.assignments[best].end) {
+                    best = Some(elf);
+                }
+                i += 1;
+            }
+
+            if let Some(best) = best {
+                let end = self.assignments[best].end as u128;
+                if end >= next {
+                    cover.push(best);
+                    next = end + 1;
+                }
+            }
+        }
+
+        cover.sort();
+        cover
+    }
+
+    // One line per segment with its sections, the number of elves and a bar scaled to `width`
+    pub fn histogram(&self, width: usize) -> String {
+        let max_elves = self.segments.iter().map(|segment| segment.elves).max().unwrap_or(0);
+        let sections_width = self.segments.iter().map(|segment| segment.sections.to_string().len()).max().unwrap_or(0);
+        let elves_width = max_elves.to_string().len();
+
+        self.segments
+            .iter()
+            .map(|segment| {
+                let bar = if max_elves == 0 { 0 } else { (segment.elves * width).div_ceil(max_elves) };
+                let line = format!(
+                    "{:<sections_width$} {:>elves_width$} {}",
+                    segment.sections.to_string(),
+                    segment.elves,
+                    "#".repeat(bar),
+                );
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intervals(intervals: &[&str]) -> Vec<Interval> {
+        intervals.iter().map(|interval| interval.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn coverage_is_split_into_segments() {
+        let map = CoverageMap::new(&intervals(&["1-3", "2-5", "8-8"]));
+
+        let segments = map.segments.iter().map(|segment| (segment.sections.to_string(), segment.elves)).collect::<Vec<_>>();
+        let expected = [("1-1", 1), ("2-3", 2), ("4-5", 1), ("6-7", 0), ("8-8", 1)]
+            .map(|(sections, elves)| (sections.to_string(), elves));
+        assert_eq!(segments, expected);
+        assert_eq!(map.span(), Some(Interval::new(1, 8)));
+    }
+
+    #[test]
+    fn segments_with_the_same_number_of_elves_are_joined() {
+        let map = CoverageMap::new(&intervals(&["1-3", "4-6"]));
+
+        assert_eq!(map.segments, vec![Segment { sections: Interval::new(1, 6), elves: 1 }]);
+    }
+
+    #[test]
+    fn uncovered_and_overlapped_sections_are_obtained() {
+        let pairs = crate::parse_pairs("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8").unwrap();
+        let map = CoverageMap::from_pairs(&pairs);
+
+        assert_eq!(map.uncovered(), vec![]);
+        assert_eq!(map.covered_by_many(), intervals(&["2-8"]));
+
+        let map = CoverageMap::new(&intervals(&["1-3", "2-5", "8-8", "10-12", "12-12"]));
+
+        assert_eq!(map.uncovered(), intervals(&["6-7", "9-9"]));
+        assert_eq!(map.covered_by_many(), intervals(&["2-3", "12-12"]));
+    }
+
+    #[test]
+    fn minimal_cover_uses_the_fewest_elves() {
+        let map = CoverageMap::new(&intervals(&["1-3", "2-5", "1-5", "4-9", "6-9"]));
+        assert_eq!(map.minimal_cover(), vec![2, 3]);
+
+        let pairs = crate::parse_pairs("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8").unwrap();
+        assert_eq!(CoverageMap::from_pairs(&pairs).minimal_cover(), vec![5, 6]);
+    }
+
+    #[test]
+    fn minimal_cover_skips_the_gaps() {
+        let map = CoverageMap::new(&intervals(&["1-3", "2-5", "8-8"]));
+
+        assert_eq!(map.minimal_cover(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn coverage_works_up_to_the_last_section() {
+        let map = CoverageMap::new(&intervals(&["0-18446744073709551615", "18446744073709551615-18446744073709551615"]));
+
+        assert_eq!(map.covered_by_many(), intervals(&["18446744073709551615-18446744073709551615"]));
+        assert_eq!(map.minimal_cover(), vec![0]);
+    }
+
+    #[test]
+    fn empty_coverage() {
+        let map = CoverageMap::new(&[]);
+
+        assert_eq!(map.span(), None);
+        assert!(map.minimal_cover().is_empty());
+        assert_eq!(map.histogram(10), "");
+    }
+
+    #[test]
+    fn coverage_is_printed_as_a_histogram() {
+        let map = CoverageMap::new(&intervals(&["1-3", "2-5", "8-8", "10-11"]));
+
+        let expected = "\
+1-1   1 ##
+2-3   2 ####
+4-5   1 ##
+6-7   0
+8-8   1 ##
+9-9   0
+10-11 1 ##";
+        assert_eq!(map.histogram(4), expected);
+    }
+}
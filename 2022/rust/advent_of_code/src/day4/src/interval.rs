@@ -0,0 +1,178 @@
+use std::cmp::{max, min};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// Inclusive range of sections, both ends are part of it
+#[derive(PartialEq, Eq, Debug, Clone, Copy, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Interval {
+    // The ends can be given in any order, `new(4, 2)` is the interval `2-4`. Parsing is stricter and
+    // rejects a reversed interval.
+    pub fn new(a: u64, b: u64) -> Self {
+        Self { start: min(a, b), end: max(a, b) }
+    }
+
+    // `0-18446744073709551615` has 2^64 sections, which doesn't fit in a u64
+    pub fn sections(&self) -> u128 {
+        (self.end - self.start) as u128 + 1
+    }
+
+    pub fn contains_section(&self, section: u64) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    // There is no gap between the intervals, `1-3` and `4-6` are adjacent
+    pub fn touches(&self, other: &Interval) -> bool {
+        self.overlaps(other)
+            || self.end.checked_add(1) == Some(other.start)
+            || other.end.checked_add(1) == Some(self.start)
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        self.overlaps(other).then(|| Interval::new(max(self.start, other.start), min(self.end, other.end)))
+    }
+
+    // Only intervals without a gap between them can be joined into a single one
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        self.touches(other).then(|| Interval::new(min(self.start, other.start), max(self.end, other.end)))
+    }
+
+    // Smallest list of disjoint intervals covering the same sections, sorted by their start
+    pub fn merge<I: IntoIterator<Item = Interval>>(intervals: I) -> Vec<Interval> {
+        let mut intervals = intervals.into_iter().collect::<Vec<Interval>>();
+        intervals.sort();
+
+        let mut merged: Vec<Interval> = Vec::new();
+        for interval in intervals {
+            match merged.last_mut().and_then(|last| last.union(&interval).map(|union| (last, union))) {
+                Some((last, union)) => *last = union,
+                None => merged.push(interval),
+            }
+        }
+
+        merged
+    }
+}
+
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').ok_or_else(|| format!("Invalid interval: {}", s))?;
+
+        let parse = |section: &str| section.trim().parse::<u64>().map_err(|_| format!("Invalid section: {}", section));
+
+        let (start, end) = (parse(start)?, parse(end)?);
+        if start > end {
+            return Err(format!("Reversed interval: {}", s));
+        }
+
+        Ok(Interval { start, end })
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(s: &str) -> Interval {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn interval_is_parsed() {
+        assert_eq!(interval("2-4"), Interval { start: 2, end: 4 });
+        assert_eq!(interval("6-6"), Interval { start: 6, end: 6 });
+        assert_eq!(interval("0-18446744073709551615"), Interval { start: 0, end: u64::MAX });
+
+        assert!("2".parse::<Interval>().is_err());
+        assert!("2-a".parse::<Interval>().is_err());
+        assert!("-1-2".parse::<Interval>().is_err());
+        assert!("0-18446744073709551616".parse::<Interval>().is_err());
+    }
+
+    #[test]
+    fn reversed_intervals_are_only_built_directly() {
+        assert_eq!("4-2".parse::<Interval>(), Err(String::from("Reversed interval: 4-2")));
+        assert_eq!(Interval::new(4, 2), interval("2-4"));
+    }
+
+    #[test]
+    fn sections_count_both_ends() {
+        assert_eq!(interval("2-4").sections(), 3);
+        assert_eq!(interval("6-6").sections(), 1);
+        assert_eq!(interval("0-18446744073709551615").sections(), 1 << 64);
+    }
+
+    #[test]
+    fn containment_is_checked() {
+        assert!(interval("2-8").contains(&interval("3-7")));
+        assert!(interval("4-6").contains(&interval("6-6")));
+        assert!(interval("4-6").contains(&interval("4-6")));
+        assert!(!interval("3-7").contains(&interval("2-8")));
+        assert!(!interval("2-6").contains(&interval("4-8")));
+
+        assert!(interval("2-4").contains_section(4));
+        assert!(!interval("2-4").contains_section(5));
+    }
+
+    #[test]
+    fn overlap_is_checked() {
+        assert!(interval("5-7").overlaps(&interval("7-9")));
+        assert!(interval("2-6").overlaps(&interval("4-8")));
+        assert!(!interval("2-4").overlaps(&interval("6-8")));
+        assert!(!interval("2-3").overlaps(&interval("4-5")));
+        assert!(interval("2-3").touches(&interval("4-5")));
+    }
+
+    #[test]
+    fn intersection_is_obtained() {
+        assert_eq!(interval("2-6").intersection(&interval("4-8")), Some(interval("4-6")));
+        assert_eq!(interval("2-8").intersection(&interval("3-7")), Some(interval("3-7")));
+        assert_eq!(interval("2-4").intersection(&interval("6-8")), None);
+    }
+
+    #[test]
+    fn union_is_obtained_when_there_is_no_gap() {
+        assert_eq!(interval("2-6").union(&interval("4-8")), Some(interval("2-8")));
+        assert_eq!(interval("2-3").union(&interval("4-5")), Some(interval("2-5")));
+        assert_eq!(interval("2-3").union(&interval("5-6")), None);
+    }
+
+    #[test]
+    fn many_intervals_are_merged() {
+        let intervals = ["10-12", "1-3", "2-5", "6-6", "8-9", "11-11"].map(interval);
+
+        assert_eq!(Interval::merge(intervals), vec![interval("1-6"), interval("8-12")]);
+        assert_eq!(Interval::merge([]), vec![]);
+    }
+
+    #[test]
+    fn full_u64_ranges_do_not_overflow() {
+        let full = interval("0-18446744073709551615");
+        let high = interval("18446744073709551000-18446744073709551615");
+
+        assert!(full.contains(&high));
+        assert_eq!(full.intersection(&high), Some(high));
+        assert_eq!(high.union(&interval("1-18446744073709550999")), Some(interval("1-18446744073709551615")));
+        assert_eq!(Interval::merge([high, full, high]), vec![full]);
+    }
+}
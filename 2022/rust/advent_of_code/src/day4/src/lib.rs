@@ -0,0 +1,87 @@
+pub mod coverage;
+pub mod interval;
+
+use aoc_common::{lines, read_input, Answer, Day, Part};
+use interval::Interval;
+
+pub const DAY: Day = Day {
+    number: 4,
+    title: "Camp Cleanup",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Assignment pairs where one fully contains the other", solve: part_one },
+        Part { description: "Assignment pairs that overlap", solve: part_two },
+    ],
+};
+
+pub fn part_one(input: &str) -> Answer {
+    let (complety_overlaps, _) = count_overlaps(input)?;
+    Ok(complety_overlaps.to_string())
+}
+
+pub fn part_two(input: &str) -> Answer {
+    let (_, overlaps) = count_overlaps(input)?;
+    Ok(overlaps.to_string())
+}
+
+// Returns the number of pairs where one assignment fully contains the other and the number of
+// pairs that overlap
+fn count_overlaps(input: &str) -> Result<(u32, u32), String> {
+    let mut complety_overlaps = 0u32;
+    let mut overlaps = 0u32;
+    for (first, second) in parse_pairs(input)? {
+        if first.contains(&second) || second.contains(&first) {
+            complety_overlaps += 1;
+        }
+
+        if first.overlaps(&second) {
+            overlaps += 1;
+        }
+    }
+
+    Ok((complety_overlaps, overlaps))
+}
+
+pub fn parse_pairs(input: &str) -> Result<Vec<(Interval, Interval)>, String> {
+    lines(input).into_iter().map(parse_pair).collect()
+}
+
+fn parse_pair(line: &str) -> Result<(Interval, Interval), String> {
+    let (first, second) = line.split_once(',').ok_or_else(|| format!("Invalid pair of assignments: {}", line))?;
+
+    Ok((first.parse()?, second.parse()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elf_sectors_are_calculates() {
+        let elf_sector = "2-4".parse::<Interval>().unwrap();
+
+        assert_eq!(elf_sector, Interval::new(2, 4))
+    }
+
+    #[test]
+    fn overlapping_assignments_are_counted() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+
+        assert_eq!(part_one(input), Ok(String::from("2")));
+        assert_eq!(part_two(input), Ok(String::from("4")));
+    }
+
+    #[test]
+    fn huge_assignments_are_counted() {
+        let input = "1-4000000000,2-3999999999\n0-18446744073709551615,18446744073709551615-18446744073709551615";
+
+        assert_eq!(part_one(input), Ok(String::from("2")));
+        assert_eq!(part_two(input), Ok(String::from("2")));
+    }
+
+    #[test]
+    fn invalid_pairs_are_reported() {
+        assert!(part_one("2-4").is_err());
+        assert!(part_one("2-4,6-x").is_err());
+    }
+}
@@ -1,55 +1,46 @@
-use std::fs;
+use std::env;
+use std::process;
 
-fn main() {
-    let input = fs::read_to_string("src/day4/input/input.txt")
-        .expect("Cannot open input file");
-
-    let lines = input
-        .trim()
-        .split('\n')
-        .map(|line| line.to_string())
-        .collect::<Vec<String>>();
-
-    let mut complety_overlaps = 0u32;
-    let mut overlaps = 0u32;
-    for line in lines {
-        let mut elf_sectors = line.split(',')
-            .map(|elf_sectors| get_elf_sector(elf_sectors.to_string()))
-            .collect::<Vec<Vec<u32>>>();
-
-        // Order the sectors so the shorted one is always the first one. That's the one that could
-        //  be contained in the other
-        elf_sectors.sort_by_key(|a| a.len());
-
-        // Check if the shorter list of sectors is contained in the larger one
-        if elf_sectors[0].iter().all(|sec| elf_sectors[1].contains(sec)) {
-            complety_overlaps += 1;
-        }
+use aoc_common::read_input;
+use day4::coverage::CoverageMap;
+use day4::parse_pairs;
+
+const USAGE: &str = "Usage: day4 [coverage]";
 
-        if elf_sectors[0].iter().any(|sec| elf_sectors[1].contains(sec)) {
-            overlaps += 1;
+const HISTOGRAM_WIDTH: usize = 50;
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    match args.iter().map(String::as_str).collect::<Vec<&str>>().as_slice() {
+        [] => aoc_common::run_day(&day4::DAY),
+        ["coverage"] => print_coverage(),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
         }
     }
-
-    println!("Part one: There are {} assignments that fully contains the other", complety_overlaps);
-    println!("Part two: There are {} assignments that overlap", overlaps);
 }
 
-fn get_elf_sector(sec: String) -> Vec<u32> {
-    let mut s = sec.split('-').map(|s| s.parse::<u32>().unwrap()).collect::<Vec<u32>>();
-    s.sort();
+fn print_coverage() {
+    let input = read_input!();
 
-    (s[0]..s[1] + 1).collect()
-}
+    let pairs = parse_pairs(&input).unwrap_or_else(|error| {
+        eprintln!("Cannot parse the assignments: {}", error);
+        process::exit(1);
+    });
+    let map = CoverageMap::from_pairs(&pairs);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    println!("Span: {}", list(map.span()));
+    println!("Covered by nobody: {}", list(map.uncovered()));
+    println!("Covered by more than one elf: {}", list(map.covered_by_many()));
+    println!("Fewest elves covering every section: {}", list(map.minimal_cover()));
 
-    #[test]
-    fn elf_sectors_are_calculates() {
-        let elf_sector = get_elf_sector(String::from("2-4"));
+    println!("\n{}", map.histogram(HISTOGRAM_WIDTH));
+}
 
-        assert_eq!(elf_sector, vec![2, 3, 4])
-    }
+fn list<T: ToString, I: IntoIterator<Item = T>>(items: I) -> String {
+    let items = items.into_iter().map(|item| item.to_string()).collect::<Vec<String>>();
+
+    if items.is_empty() { String::from("none") } else { items.join(", ") }
 }
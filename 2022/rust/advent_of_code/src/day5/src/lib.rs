@@ -0,0 +1,463 @@
+pub mod stacks;
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use aoc_common::{blocks, read_input, Answer, Day, Part};
+use regex::Regex;
+use stacks::{CrateMover, Stacks};
+
+pub const DAY: Day = Day {
+    number: 5,
+    title: "Supply Stacks",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Crates on top of each stack with the CrateMover 9000", solve: part_one },
+        Part { description: "Crates on top of each stack with the CrateMover 9001", solve: part_two },
+    ],
+};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Instr {
+    pub amount: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+// Where an instruction can't be parsed, both line and column start at 1
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn instruction_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+
+    REGEX.get_or_init(|| Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap())
+}
+
+impl Instr {
+    // Parses the instruction found at `line` of the input
+    pub fn parse(s: &str, line: usize) -> Result<Self, ParseError> {
+        let error = |column: usize, message: String| ParseError { line, column, message };
+
+        let caps = instruction_regex()
+            .captures(s)
+            .ok_or_else(|| error(1, format!("Expected \"move <amount> from <stack> to <stack>\": {}", s)))?;
+
+        let mut numbers = [0usize; 3];
+        for (i, number) in numbers.iter_mut().enumerate() {
+            let m = caps.get(i + 1).unwrap();
+            *number = m.as_str().parse().map_err(|_| error(m.start() + 1, format!("Number too big: {}", m.as_str())))?;
+        }
+
+        Ok(Instr { amount: numbers[0], from: numbers[1], to: numbers[2] })
+    }
+}
+
+impl FromStr for Instr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Instr::parse(s, 1)
+    }
+}
+
+impl Display for Instr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.amount, self.from, self.to)
+    }
+}
+
+// Why an instruction can't be applied to the stacks
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum MoveError {
+    NoSuchStack(usize),
+    NotEnoughCrates { stack: usize, needed: usize, available: usize },
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveError::NoSuchStack(stack) => write!(f, "There is no stack {}", stack),
+            MoveError::NotEnoughCrates { stack, needed, available } => {
+                write!(f, "Stack {} has {} crates but {} must be moved", stack, available, needed)
+            }
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Answer {
+    let mut stacks = load(input, CrateMover::CrateMover9000)?;
+    stacks.run().map_err(|error| error.to_string())?;
+
+    Ok(stacks.tops())
+}
+
+pub fn part_two(input: &str) -> Answer {
+    let mut stacks = load(input, CrateMover::CrateMover9001)?;
+    stacks.run().map_err(|error| error.to_string())?;
+
+    Ok(stacks.tops())
+}
+
+// Stacks of the drawing with the instructions ready to be applied
+pub fn load(input: &str, crane: CrateMover) -> Result<Stacks, String> {
+    let (crates_stacks_draw, instructions) = split_input(input)?;
+
+    // Both parts are slices of the input, so each of them starts after the lines before it
+    let first_line = |part: &str| input[..part.as_ptr() as usize - input.as_ptr() as usize].lines().count() + 1;
+
+    let crate_stacks = parse_crate_stacks(crates_stacks_draw, first_line(crates_stacks_draw)).map_err(|error| error.to_string())?;
+    let instructions = parse_instructions(instructions, first_line(instructions)).map_err(|error| error.to_string())?;
+
+    Ok(Stacks::new(crate_stacks, instructions, crane))
+}
+
+// Parses all the instructions, `first_line` is the line of the input where they start
+pub fn parse_instructions(instructions: &str, first_line: usize) -> Result<Vec<Instr>, ParseError> {
+    instructions
+        .lines()
+        .enumerate()
+        .filter(|(_, instruction)| !instruction.trim().is_empty())
+        .map(|(i, instruction)| Instr::parse(instruction.trim_end(), first_line + i))
+        .collect()
+}
+
+// Splits the input into the crate stacks drawing and the instructions
+fn split_input(input: &str) -> Result<(&str, &str), String> {
+    match blocks(input)[..] {
+        [crates_stacks_draw, instructions] => Ok((crates_stacks_draw, instructions)),
+        _ => Err(String::from("Expected a drawing of the stacks and a list of instructions")),
+    }
+}
+
+pub fn obtain_crate_stacks(crate_stacks_draw: &str) -> Result<Vec<Vec<String>>, ParseError> {
+    parse_crate_stacks(crate_stacks_draw, 1)
+}
+
+// Parses the drawing of the stacks, `first_line` is the line of the input where it starts. The last
+// row numbers the stacks from 1, the rows above it have a `[X]` crate or a blank for every stack.
+pub fn parse_crate_stacks(crate_stacks_draw: &str, first_line: usize) -> Result<Vec<Vec<String>>, ParseError> {
+    let rows = crate_stacks_draw.lines().collect::<Vec<&str>>();
+    let Some((numbers, crates)) = rows.split_last() else {
+        return Err(ParseError { line: first_line, column: 1, message: String::from("Expected a drawing of the stacks") });
+    };
+
+    let error = |row: usize, column: usize, message: String| ParseError { line: first_line + row, column, message };
+
+    // Get the num of stacks from the numbers of the last row, which must count from 1
+    let mut stacks_num = 0;
+    for (column, number) in words(numbers) {
+        if number.parse::<usize>() != Ok(stacks_num + 1) {
+            return Err(error(crates.len(), column, format!("Expected stack number {}: {}", stacks_num + 1, number)));
+        }
+        stacks_num += 1;
+    }
+    if stacks_num == 0 {
+        return Err(error(crates.len(), 1, String::from("Expected the numbers of the stacks")));
+    }
+
+    let mut stacks = vec![Vec::new(); stacks_num];
+
+    // From the bottom row up, so that the crates are pushed in their order on the stack
+    for (row, line) in crates.iter().enumerate().rev() {
+        let chars = line.chars().collect::<Vec<char>>();
+
+        for (i, c) in chars.chunks(4).enumerate() {
+            let column = 4 * i + 1;
+            let c = c.iter().collect::<String>();
+
+            if c.trim().is_empty() {
+                continue;
+            }
+            if i >= stacks_num {
+                return Err(error(row, column, format!("Crate outside of the {} stacks: {}", stacks_num, c.trim())));
+            }
+
+            match c.trim_end().strip_prefix('[').and_then(|c| c.strip_suffix(']')) {
+                Some(name) if !name.trim().is_empty() => stacks[i].push(name.to_string()),
+                _ => return Err(error(row, column, format!("Expected a crate like [A]: {}", c.trim_end()))),
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+// Words of the line with the column where each of them starts
+fn words(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split(' ')
+        .scan(1, |column, word| {
+            let start = *column;
+            *column += word.chars().count() + 1;
+            Some((start, word))
+        })
+        .filter(|(_, word)| !word.is_empty())
+}
+
+fn check_move(stacks: &[Vec<String>], instructions: Instr) -> Result<(), MoveError> {
+    for stack in [instructions.from, instructions.to] {
+        if stack == 0 || stack > stacks.len() {
+            return Err(MoveError::NoSuchStack(stack));
+        }
+    }
+
+    let available = stacks[instructions.from - 1].len();
+    if instructions.amount > available {
+        return Err(MoveError::NotEnoughCrates { stack: instructions.from, needed: instructions.amount, available });
+    }
+
+    Ok(())
+}
+
+fn move_crates_crate_mover_9000(stacks: &mut [Vec<String>], instructions: Instr) -> Result<(), MoveError> {
+    check_move(stacks, instructions)?;
+
+    for _ in 0..instructions.amount {
+        let crate_to_move = stacks[instructions.from - 1].pop();
+        stacks[instructions.to - 1].extend(crate_to_move);
+    }
+
+    Ok(())
+}
+
+fn move_crates_crate_mover_9001(stacks: &mut [Vec<String>], instructions: Instr) -> Result<(), MoveError> {
+    check_move(stacks, instructions)?;
+
+    let from_stack = &mut stacks[instructions.from - 1];
+    let crates_to_move = from_stack
+        .drain(from_stack.len() - instructions.amount..from_stack.len())
+        .collect::<Vec<String>>();
+
+    let to_stack = &mut stacks[instructions.to - 1];
+    to_stack.extend(crates_to_move);
+
+    Ok(())
+}
+
+// Empty stacks have no crate on top and are skipped
+fn get_stack_tops(stacks: &Vec<Vec<String>>) -> Vec<String> {
+    let mut stack_tops: Vec<String> = Vec::new();
+
+    for stack in stacks {
+        stack_tops.extend(stack.last().cloned());
+    }
+
+    stack_tops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crates_stacks_are_obtained_from_the_crates_draw() {
+        let input = String::from("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ");
+
+        let crate_stacks: Vec<Vec<String>> = obtain_crate_stacks(&input).unwrap();
+
+        assert_eq!(crate_stacks[0], vec!["Z", "N"]);
+        assert_eq!(crate_stacks[1], vec!["M", "C", "D"]);
+        assert_eq!(crate_stacks[2], vec!["P"])
+    }
+
+    #[test]
+    fn malformed_drawings_are_reported_with_their_position() {
+        let error = |drawing: &str| obtain_crate_stacks(drawing).unwrap_err().to_string();
+
+        assert_eq!(error("[Z] [M] [P]\n 1   2   4 "), "Line 2, column 10: Expected stack number 3: 4");
+        assert_eq!(error("[Z] [M] [P]\n 1   x   3 "), "Line 2, column 6: Expected stack number 2: x");
+        assert_eq!(error("[Z] [M] [P]\n    "), "Line 2, column 1: Expected the numbers of the stacks");
+        assert_eq!(error("[Z] [M] [P]\n 1   2 "), "Line 1, column 9: Crate outside of the 2 stacks: [P]");
+        assert_eq!(error("[Z] M   [P]\n 1   2   3 "), "Line 1, column 5: Expected a crate like [A]: M");
+        assert_eq!(error(""), "Line 1, column 1: Expected a drawing of the stacks");
+    }
+
+    #[test]
+    fn truncated_drawings_are_reported() {
+        // The row with the numbers of the stacks is missing
+        assert_eq!(
+            obtain_crate_stacks("    [D]    \n[N] [C]    ").unwrap_err().to_string(),
+            "Line 2, column 1: Expected stack number 1: [N]"
+        );
+
+        let input = "\n    [D]    \n[N] [C]    \n\nmove 1 from 2 to 1\n";
+        assert_eq!(part_one(input).unwrap_err(), "Line 3, column 1: Expected stack number 1: [N]");
+    }
+
+    #[test]
+    fn instruction_can_be_decoded() {
+        let instr = "move 1 from 2 to 1".parse::<Instr>();
+        assert_eq!(instr, Ok(Instr { amount: 1, from: 2, to: 1 }));
+
+        let instr = "move 3 from 1 to 3".parse::<Instr>();
+        assert_eq!(instr, Ok(Instr { amount: 3, from: 1, to: 3 }));
+
+        let instr = "move 2 from 2 to 1".parse::<Instr>();
+        assert_eq!(instr, Ok(Instr { amount: 2, from: 2, to: 1 }));
+
+        let instr = "move 1 from 1 to 2".parse::<Instr>();
+        assert_eq!(instr, Ok(Instr { amount: 1, from: 1, to: 2 }));
+    }
+
+    #[test]
+    fn invalid_instructions_are_reported_with_their_position() {
+        let instructions = "move 1 from 2 to 1\nmove 3 from one to 3\n\nmove 99999999999999999999 from 1 to 2";
+
+        assert_eq!(
+            parse_instructions(instructions, 6),
+            Err(ParseError {
+                line: 7,
+                column: 1,
+                message: String::from("Expected \"move <amount> from <stack> to <stack>\": move 3 from one to 3"),
+            })
+        );
+
+        let error = parse_instructions(&instructions.replace("one", "1"), 6).unwrap_err();
+        assert_eq!((error.line, error.column), (9, 6));
+        assert_eq!(error.to_string(), "Line 9, column 6: Number too big: 99999999999999999999");
+    }
+
+    #[test]
+    fn instructions_are_parsed_with_the_line_of_the_input() {
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to x\n";
+
+        assert_eq!(part_one(input).unwrap_err(), "Line 7, column 1: Expected \"move <amount> from <stack> to <stack>\": move 3 from 1 to x");
+    }
+
+    #[test]
+    fn moves_that_cannot_be_done_are_errors() {
+        for mover in [move_crates_crate_mover_9000, move_crates_crate_mover_9001] {
+            let mut crate_stacks = vec![vec![String::from("Z"), String::from("N")], vec![]];
+
+            assert_eq!(
+                mover(&mut crate_stacks, Instr { amount: 3, from: 1, to: 2 }),
+                Err(MoveError::NotEnoughCrates { stack: 1, needed: 3, available: 2 })
+            );
+            assert_eq!(mover(&mut crate_stacks, Instr { amount: 1, from: 2, to: 1 }), Err(MoveError::NotEnoughCrates { stack: 2, needed: 1, available: 0 }));
+            assert_eq!(mover(&mut crate_stacks, Instr { amount: 1, from: 1, to: 3 }), Err(MoveError::NoSuchStack(3)));
+            assert_eq!(mover(&mut crate_stacks, Instr { amount: 1, from: 0, to: 1 }), Err(MoveError::NoSuchStack(0)));
+
+            // Failed moves leave the stacks untouched
+            assert_eq!(crate_stacks, vec![vec![String::from("Z"), String::from("N")], vec![]]);
+        }
+    }
+
+    #[test]
+    fn crates_are_moved_with_crate_mover_9000_based_on_instruction() {
+        let mut crate_stacks = vec![
+            vec![String::from("Z"), String::from("N")],
+            vec![String::from("M"), String::from("C"), String::from("D")],
+            vec![String::from("P")]];
+
+        move_crates_crate_mover_9000(&mut crate_stacks, Instr { amount: 1, from: 2, to: 1 }).unwrap();
+
+        assert_eq!(crate_stacks, vec![
+            vec![String::from("Z"), String::from("N"), String::from("D")],
+            vec![String::from("M"), String::from("C")],
+            vec![String::from("P")]]);
+
+        move_crates_crate_mover_9000(&mut crate_stacks, Instr { amount: 3, from: 1, to: 3 }).unwrap();
+
+        assert_eq!(crate_stacks, vec![
+            vec![],
+            vec![String::from("M"), String::from("C")],
+            vec![String::from("P"), String::from("D"), String::from("N"), String::from("Z")]]);
+
+        move_crates_crate_mover_9000(&mut crate_stacks, Instr { amount: 2, from: 2, to: 1 }).unwrap();
+
+        assert_eq!(crate_stacks, vec![
+            vec![String::from("C"), String::from("M")],
+            vec![],
+            vec![String::from("P"), String::from("D"), String::from("N"), String::from("Z")]]);
+
+        move_crates_crate_mover_9000(&mut crate_stacks, Instr { amount: 1, from: 1, to: 2 }).unwrap();
+
+        assert_eq!(crate_stacks, vec![
+            vec![String::from("C")],
+            vec![String::from("M")],
+            vec![String::from("P"), String::from("D"), String::from("N"), String::from("Z")]]);
+    }
+
+    #[test]
+    fn get_all_stack_tops() {
+        let stacks = vec![
+            vec![String::from("C")],
+            vec![String::from("M")],
+            vec![String::from("P"), String::from("D"), String::from("N"), String::from("Z")]];
+
+        let stack_tops = get_stack_tops(&stacks);
+
+        assert_eq!(stack_tops, vec![String::from("C"), String::from("M"), String::from("Z")])
+    }
+
+    #[test]
+    fn empty_stacks_have_no_top() {
+        let stacks = vec![vec![], vec![String::from("M")], vec![]];
+        assert_eq!(get_stack_tops(&stacks), vec![String::from("M")]);
+
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 3 to 1\n";
+        assert_eq!(part_one(input), Ok(String::from("PD")));
+        assert_eq!(part_two(input), Ok(String::from("PD")));
+    }
+
+    #[test]
+    fn crates_are_moved_with_crate_mover_9001_based_on_instruction() {
+        let mut crate_stacks = vec![
+            vec![String::from("Z"), String::from("N")],
+            vec![String::from("M"), String::from("C"), String::from("D")],
+            vec![String::from("P")]];
+
+        move_crates_crate_mover_9001(&mut crate_stacks, Instr { amount: 1, from: 2, to: 1 }).unwrap();
+
+        assert_eq!(crate_stacks, vec![
+            vec![String::from("Z"), String::from("N"), String::from("D")],
+            vec![String::from("M"), String::from("C")],
+            vec![String::from("P")]]);
+
+        move_crates_crate_mover_9001(&mut crate_stacks, Instr { amount: 3, from: 1, to: 3 }).unwrap();
+
+        assert_eq!(crate_stacks, vec![
+            vec![],
+            vec![String::from("M"), String::from("C")],
+            vec![String::from("P"), String::from("Z"), String::from("N"), String::from("D")]]);
+
+        move_crates_crate_mover_9001(&mut crate_stacks, Instr { amount: 2, from: 2, to: 1 }).unwrap();
+
+        assert_eq!(crate_stacks, vec![
+            vec![String::from("M"), String::from("C")],
+            vec![],
+            vec![String::from("P"), String::from("Z"), String::from("N"), String::from("D")]]);
+
+        move_crates_crate_mover_9001(&mut crate_stacks, Instr { amount: 1, from: 1, to: 2 }).unwrap();
+
+        assert_eq!(crate_stacks, vec![
+            vec![String::from("M")],
+            vec![String::from("C")],
+            vec![String::from("P"), String::from("Z"), String::from("N"), String::from("D")]]);
+    }
+
+    #[test]
+    fn stack_tops_are_obtained_after_moving_the_crates() {
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n";
+
+        assert_eq!(part_one(input), Ok(String::from("CMZ")));
+        assert_eq!(part_two(input), Ok(String::from("MCD")));
+
+        let input = input.replace('\n', "\r\n");
+        assert_eq!(part_one(&input), Ok(String::from("CMZ")));
+        assert_eq!(part_two(&input), Ok(String::from("MCD")));
+    }
+}
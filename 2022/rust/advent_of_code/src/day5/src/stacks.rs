@@ -0,0 +1,326 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+use crate::{get_stack_tops, move_crates_crate_mover_9000, move_crates_crate_mover_9001, Instr, MoveError};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CrateMover {
+    // Moves the crates one at a time
+    CrateMover9000,
+    // Moves all the crates at once, keeping their order
+    CrateMover9001,
+}
+
+// Instruction that couldn't be applied, with the stacks as they were before trying it
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ExecutionError {
+    // Position of the instruction in the list, starting at 0. It is reported starting at 1, the way
+    // the steps are counted when the stacks are shown
+    pub index: usize,
+    pub instr: Instr,
+    pub crane: CrateMover,
+    pub error: MoveError,
+    pub stacks: Vec<Vec<String>>,
+}
+
+impl Display for ExecutionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Instruction {} ({}) cannot be applied with the {:?}: {}\n{}",
+            self.index + 1,
+            self.instr,
+            self.crane,
+            self.error,
+            render(&self.stacks)
+        )
+    }
+}
+
+impl Error for ExecutionError {}
+
+// Crates taken by an instruction, in the order they had on the origin stack, to be able to undo it
+struct Step {
+    instr: Instr,
+    crates: Vec<String>,
+}
+
+pub struct Stacks {
+    stacks: Vec<Vec<String>>,
+    instructions: Vec<Instr>,
+    crane: CrateMover,
+    history: Vec<Step>,
+}
+
+impl Stacks {
+    pub fn new(stacks: Vec<Vec<String>>, instructions: Vec<Instr>, crane: CrateMover) -> Self {
+        Self { stacks, instructions, crane, history: Vec::new() }
+    }
+
+    pub fn stacks(&self) -> &[Vec<String>] {
+        &self.stacks
+    }
+
+    // Number of instructions applied so far
+    pub fn position(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn steps(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position() == self.steps()
+    }
+
+    pub fn next_instruction(&self) -> Option<&Instr> {
+        self.instructions.get(self.position())
+    }
+
+    // Applies the next instruction, returns false if all of them were already applied. The stacks
+    // don't change when the instruction can't be applied.
+    pub fn step_forward(&mut self) -> Result<bool, ExecutionError> {
+        let Some(instr) = self.next_instruction().copied() else {
+            return Ok(false);
+        };
+
+        let crates = self
+            .stacks
+            .get(instr.from.wrapping_sub(1))
+            .map(|from_stack| from_stack[from_stack.len().saturating_sub(instr.amount)..].to_vec())
+            .unwrap_or_default();
+
+        let result = match self.crane {
+            CrateMover::CrateMover9000 => move_crates_crate_mover_9000(&mut self.stacks, instr),
+            CrateMover::CrateMover9001 => move_crates_crate_mover_9001(&mut self.stacks, instr),
+        };
+
+        result.map_err(|error| ExecutionError {
+            index: self.position(),
+            instr,
+            crane: self.crane,
+            error,
+            stacks: self.stacks.clone(),
+        })?;
+
+        self.history.push(Step { instr, crates });
+
+        Ok(true)
+    }
+
+    // Undoes the last applied instruction, returns false if none was applied
+    pub fn step_back(&mut self) -> bool {
+        let Some(step) = self.history.pop() else {
+            return false;
+        };
+
+        let to_stack = &mut self.stacks[step.instr.to - 1];
+        to_stack.truncate(to_stack.len() - step.crates.len());
+        self.stacks[step.instr.from - 1].extend(step.crates);
+
+        true
+    }
+
+    pub fn run(&mut self) -> Result<(), ExecutionError> {
+        while self.step_forward()? {}
+
+        Ok(())
+    }
+
+    pub fn rewind(&mut self) {
+        while self.step_back() {}
+    }
+
+    pub fn tops(&self) -> String {
+        get_stack_tops(&self.stacks).join("")
+    }
+
+    pub fn render(&self) -> String {
+        render(&self.stacks)
+    }
+
+    // Replays all the instructions from the start, redrawing the stacks after every one of them
+    pub fn replay<W: Write>(&mut self, out: &mut W, delay: Duration) -> Result<(), Box<dyn Error>> {
+        self.rewind();
+
+        loop {
+            // Clear the screen and move the cursor to the top left corner
+            write!(out, "\x1b[2J\x1b[H")?;
+            match self.history.last() {
+                Some(step) => writeln!(out, "Step {}/{}: {}", self.position(), self.steps(), step.instr)?,
+                None => writeln!(out, "Step 0/{}", self.steps())?,
+            }
+            writeln!(out, "\n{}", self.render())?;
+            out.flush()?;
+
+            if !self.step_forward()? {
+                break;
+            }
+            thread::sleep(delay);
+        }
+
+        writeln!(out, "\nCrates on top: {}", self.tops())?;
+
+        Ok(())
+    }
+}
+
+// Drawing of the stacks in the same format as the puzzle input, crates first and then the numbers
+// of the stacks
+pub fn render(stacks: &[Vec<String>]) -> String {
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+
+    let mut rows = Vec::new();
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(c) => format!("[{}]", c),
+                None => String::from("   "),
+            })
+            .collect::<Vec<String>>();
+        rows.push(row.join(" "));
+    }
+
+    let numbers = (1..=stacks.len()).map(|number| format!(" {} ", number)).collect::<Vec<String>>();
+    rows.push(numbers.join(" "));
+
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::obtain_crate_stacks;
+
+    const DRAWING: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
+
+    fn instructions() -> Vec<Instr> {
+        vec![
+            Instr { amount: 1, from: 2, to: 1 },
+            Instr { amount: 3, from: 1, to: 3 },
+            Instr { amount: 2, from: 2, to: 1 },
+            Instr { amount: 1, from: 1, to: 2 },
+        ]
+    }
+
+    fn stacks(crane: CrateMover) -> Stacks {
+        Stacks::new(obtain_crate_stacks(DRAWING).unwrap(), instructions(), crane)
+    }
+
+    #[test]
+    fn instructions_are_applied_step_by_step() {
+        let mut stacks = stacks(CrateMover::CrateMover9000);
+
+        assert_eq!(stacks.step_forward(), Ok(true));
+        assert_eq!(stacks.position(), 1);
+        assert_eq!(stacks.tops(), "DCP");
+
+        stacks.run().unwrap();
+        assert!(stacks.is_finished());
+        assert_eq!(stacks.step_forward(), Ok(false));
+        assert_eq!(stacks.tops(), "CMZ");
+
+        let mut stacks = self::stacks(CrateMover::CrateMover9001);
+        stacks.run().unwrap();
+        assert_eq!(stacks.tops(), "MCD");
+    }
+
+    #[test]
+    fn steps_can_be_undone() {
+        for crane in [CrateMover::CrateMover9000, CrateMover::CrateMover9001] {
+            let mut stacks = stacks(crane);
+            let mut states = vec![stacks.stacks().to_vec()];
+            while stacks.step_forward().unwrap() {
+                states.push(stacks.stacks().to_vec());
+            }
+
+            while let Some(state) = states.pop() {
+                assert_eq!(stacks.stacks(), state);
+                stacks.step_back();
+            }
+
+            assert_eq!(stacks.position(), 0);
+            assert!(!stacks.step_back());
+        }
+    }
+
+    #[test]
+    fn stacks_are_rendered_as_the_input_drawing() {
+        let stacks = stacks(CrateMover::CrateMover9000);
+
+        assert_eq!(stacks.render(), DRAWING);
+    }
+
+    #[test]
+    fn rendered_stacks_can_be_parsed_back() {
+        for crane in [CrateMover::CrateMover9000, CrateMover::CrateMover9001] {
+            let mut stacks = stacks(crane);
+            loop {
+                assert_eq!(obtain_crate_stacks(&stacks.render()).unwrap(), stacks.stacks());
+                if !stacks.step_forward().unwrap() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn replay_draws_every_step() {
+        let mut stacks = stacks(CrateMover::CrateMover9000);
+        stacks.step_forward().unwrap();
+
+        let mut out = Vec::new();
+        stacks.replay(&mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(out.matches("\x1b[2J").count(), 5);
+        assert!(out.contains("Step 0/4\n\n    [D]    \n"));
+        assert!(out.contains("Step 4/4: move 1 from 1 to 2\n\n        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n"));
+        assert!(out.ends_with("Crates on top: CMZ\n"));
+    }
+
+    #[test]
+    fn failing_instruction_is_reported_with_the_stacks() {
+        for crane in [CrateMover::CrateMover9000, CrateMover::CrateMover9001] {
+            let mut instructions = instructions();
+            instructions.insert(2, Instr { amount: 5, from: 3, to: 1 });
+            let mut stacks = Stacks::new(obtain_crate_stacks(DRAWING).unwrap(), instructions, crane);
+
+            let error = stacks.run().unwrap_err();
+
+            assert_eq!(error.index, 2);
+            assert_eq!(error.instr, Instr { amount: 5, from: 3, to: 1 });
+            assert_eq!(error.error, MoveError::NotEnoughCrates { stack: 3, needed: 5, available: 4 });
+            assert_eq!(error.stacks, stacks.stacks());
+            assert_eq!(stacks.position(), 2);
+            assert!(error.to_string().starts_with(&format!(
+                "Instruction 3 (move 5 from 3 to 1) cannot be applied with the {:?}: Stack 3 has 4 crates but 5 must be moved\n",
+                crane
+            )));
+        }
+    }
+
+    #[test]
+    fn emptied_stacks_are_skipped_in_the_tops() {
+        let instructions = vec![Instr { amount: 1, from: 3, to: 2 }, Instr { amount: 2, from: 1, to: 2 }];
+        let mut stacks = Stacks::new(obtain_crate_stacks(DRAWING).unwrap(), instructions, CrateMover::CrateMover9000);
+
+        stacks.run().unwrap();
+        assert_eq!(stacks.tops(), "Z");
+        assert!(stacks.render().ends_with("[M]    \n 1   2   3 "));
+    }
+
+    #[test]
+    fn instruction_on_a_missing_stack_is_reported() {
+        let instructions = vec![Instr { amount: 1, from: 4, to: 1 }];
+        let mut stacks = Stacks::new(obtain_crate_stacks(DRAWING).unwrap(), instructions, CrateMover::CrateMover9000);
+
+        assert_eq!(stacks.step_forward().unwrap_err().error, MoveError::NoSuchStack(4));
+        assert_eq!(stacks.render(), DRAWING);
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::VecDeque;
+use std::io::{self, BufReader, Read};
+
+use aoc_common::{read_input, Answer, Day, Part};
+
+pub const DAY: Day = Day {
+    number: 6,
+    title: "Tuning Trouble",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Characters processed before the first start-of-packet marker", solve: part_one },
+        Part { description: "Characters processed before the first start-of-message marker", solve: part_two },
+    ],
+};
+
+const START_OF_PACKET_SIZE: usize = 4;
+const START_OF_MESSAGE_SIZE: usize = 14;
+
+// A marker of `size` distinct bytes, `end` is the number of bytes processed up to its last byte
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Marker {
+    pub size: usize,
+    pub end: usize,
+}
+
+// Last `size` bytes of the stream, counting how many times every byte value is in it
+struct Window {
+    size: usize,
+    counts: [u32; 256],
+    // Byte values found more than once
+    repeated: usize,
+}
+
+impl Window {
+    fn new(size: usize) -> Self {
+        Self { size, counts: [0; 256], repeated: 0 }
+    }
+
+    fn add(&mut self, byte: u8) {
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.repeated += 1;
+        }
+    }
+
+    fn remove(&mut self, byte: u8) {
+        self.counts[byte as usize] -= 1;
+        if self.counts[byte as usize] == 1 {
+            self.repeated -= 1;
+        }
+    }
+}
+
+// Finds markers of several sizes at once, updating every window in constant time per byte
+pub struct MarkerDetector {
+    windows: Vec<Window>,
+    // Enough bytes to know which one leaves every window
+    last_bytes: VecDeque<u8>,
+    // Size of the longest window, only its bytes are kept
+    longest: usize,
+    processed: usize,
+}
+
+impl MarkerDetector {
+    // Marker sizes must be at least 1
+    pub fn new(sizes: &[usize]) -> Result<Self, String> {
+        if sizes.contains(&0) {
+            return Err(String::from("Marker sizes must be at least 1"));
+        }
+
+        let longest = sizes.iter().copied().max().unwrap_or(0);
+
+        Ok(Self {
+            windows: sizes.iter().map(|&size| Window::new(size)).collect(),
+            last_bytes: VecDeque::with_capacity(longest + 1),
+            longest,
+            processed: 0,
+        })
+    }
+
+    // Adds the next byte of the stream, returning the markers that end with it
+    pub fn push(&mut self, byte: u8) -> Vec<Marker> {
+        self.processed += 1;
+        self.last_bytes.push_front(byte);
+
+        let mut markers = Vec::new();
+        for window in self.windows.iter_mut() {
+            window.add(byte);
+            if let Some(&leaving) = self.last_bytes.get(window.size) {
+                window.remove(leaving);
+            }
+
+            if self.processed >= window.size && window.repeated == 0 {
+                markers.push(Marker { size: window.size, end: self.processed });
+            }
+        }
+
+        // Only the bytes still in the longest window are needed
+        self.last_bytes.truncate(self.longest);
+
+        markers
+    }
+
+    pub fn processed(&self) -> usize {
+        self.processed
+    }
+}
+
+// Every marker of the stream, sorted by position
+pub fn find_markers<I: IntoIterator<Item = u8>>(bytes: I, sizes: &[usize]) -> Result<Vec<Marker>, String> {
+    let mut detector = MarkerDetector::new(sizes)?;
+
+    Ok(bytes.into_iter().flat_map(|byte| detector.push(byte)).collect())
+}
+
+// Position of the first marker of every size, in the same order as the sizes. The stream is read
+// until all of them are found.
+pub fn first_markers<I: IntoIterator<Item = u8>>(bytes: I, sizes: &[usize]) -> Result<Vec<Option<usize>>, String> {
+    let mut detector = MarkerDetector::new(sizes)?;
+    let mut first = vec![None; sizes.len()];
+
+    for byte in bytes {
+        if first.iter().all(Option::is_some) {
+            break;
+        }
+
+        for marker in detector.push(byte) {
+            for (i, &size) in sizes.iter().enumerate() {
+                if size == marker.size && first[i].is_none() {
+                    first[i] = Some(marker.end);
+                }
+            }
+        }
+    }
+
+    Ok(first)
+}
+
+// Invalid marker sizes are reported as an `InvalidInput` error
+pub fn read_markers<R: Read>(reader: R, sizes: &[usize]) -> io::Result<Vec<Marker>> {
+    let mut detector = MarkerDetector::new(sizes).map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+    let mut markers = Vec::new();
+
+    for byte in BufReader::new(reader).bytes() {
+        markers.extend(detector.push(byte?));
+    }
+
+    Ok(markers)
+}
+
+pub fn part_one(input: &str) -> Answer {
+    first_marker(input, START_OF_PACKET_SIZE, "start-of-packet")
+}
+
+pub fn part_two(input: &str) -> Answer {
+    first_marker(input, START_OF_MESSAGE_SIZE, "start-of-message")
+}
+
+fn first_marker(input: &str, size: usize, name: &str) -> Answer {
+    match first_markers(input.trim().bytes(), &[size])?[..] {
+        [Some(end)] => Ok(end.to_string()),
+        _ => Err(format!("No {} marker found", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATASTREAM_INPUT: &str = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+
+    #[test]
+    fn markers_of_the_examples_are_found() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+
+        for (datastream, packet, message) in examples {
+            assert_eq!(first_markers(datastream.bytes(), &[4, 14]), Ok(vec![Some(packet), Some(message)]));
+        }
+    }
+
+    #[test]
+    fn every_marker_position_is_reported() {
+        let markers = find_markers("aabcbdd".bytes(), &[3]).unwrap();
+
+        let ends = markers.iter().map(|marker| marker.end).collect::<Vec<usize>>();
+        assert_eq!(ends, vec![4, 6]);
+    }
+
+    #[test]
+    fn markers_of_several_sizes_are_found_in_one_pass() {
+        let markers = find_markers("abcabd".bytes(), &[1, 3, 4]).unwrap();
+
+        assert_eq!(markers.iter().filter(|marker| marker.size == 1).count(), 6);
+        let longer = markers.into_iter().filter(|marker| marker.size > 1).collect::<Vec<Marker>>();
+        assert_eq!(
+            longer,
+            vec![
+                Marker { size: 3, end: 3 },
+                Marker { size: 3, end: 4 },
+                Marker { size: 3, end: 5 },
+                Marker { size: 3, end: 6 },
+                Marker { size: 4, end: 6 },
+            ]
+        );
+    }
+
+    #[test]
+    fn markers_are_found_in_a_reader() {
+        let markers = read_markers(DATASTREAM_INPUT.as_bytes(), &[14]).unwrap();
+
+        assert_eq!(markers.first(), Some(&Marker { size: 14, end: 29 }));
+    }
+
+    #[test]
+    fn markers_can_use_any_byte() {
+        let bytes = (0..=255u8).chain(0..=255u8).collect::<Vec<u8>>();
+
+        assert_eq!(first_markers(bytes.iter().copied(), &[256, 257]), Ok(vec![Some(256), None]));
+        assert_eq!(find_markers(bytes, &[256]).map(|markers| markers.len()), Ok(257));
+    }
+
+    #[test]
+    fn markers_of_size_zero_are_rejected() {
+        let error = String::from("Marker sizes must be at least 1");
+
+        assert_eq!(MarkerDetector::new(&[4, 0]).err(), Some(error.clone()));
+        assert_eq!(find_markers("abcd".bytes(), &[0]), Err(error.clone()));
+        assert_eq!(first_markers("abcd".bytes(), &[0, 4]), Err(error));
+        let read_error = read_markers("abcd".as_bytes(), &[0]).unwrap_err();
+        assert_eq!(read_error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn markers_are_found_in_the_datastream() {
+        assert_eq!(part_one(DATASTREAM_INPUT), Ok(String::from("10")));
+        assert_eq!(part_two(DATASTREAM_INPUT), Ok(String::from("29")));
+    }
+
+    #[test]
+    fn a_datastream_without_markers_is_an_error() {
+        assert!(part_one("aaaaaa").is_err());
+        assert!(part_two("abcdaaaa").is_err());
+    }
+}
@@ -1,93 +1,415 @@
-use std::any::{Any, TypeId};
-use std::cell::RefCell;
-use std::fmt::{Debug};
-use std::rc::Rc;
+use std::fmt::{Debug, Formatter};
 
-trait INode: Debug {
-    fn size(&self) -> u32;
-    fn as_any(&self) -> &dyn Any;
+// All the inodes live in a single arena and refer to each other by their position in it. Every
+// inode knows its parent, so going to `..` doesn't need to walk the tree from the root, and every
+// directory keeps the size of everything inside it up to date.
+pub type NodeId = usize;
+
+#[derive(PartialEq, Eq, Debug)]
+enum Kind {
+    Directory { children: Vec<NodeId> },
+    File,
 }
 
-type Node = Box<dyn INode>;
+#[derive(PartialEq, Eq, Debug)]
+struct Node {
+    name: String,
+    parent: Option<NodeId>,
+    kind: Kind,
+    // Size of the file, or of all the files inside the directory at any depth
+    size: u64,
+}
 
-impl PartialEq for Node {
-    fn eq(&self, other: &Self) -> bool {
-        self == other
+#[derive(PartialEq, Eq, Debug)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem {
+    pub const ROOT: NodeId = 0;
+
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![Node {
+                name: String::from("/"),
+                parent: None,
+                kind: Kind::Directory { children: Vec::new() },
+                size: 0,
+            }],
+        }
+    }
+
+    pub fn root(&self) -> DirectoryRef<'_> {
+        DirectoryRef { fs: self, id: Self::ROOT }
+    }
+
+    pub fn directory(&self, id: NodeId) -> Option<DirectoryRef<'_>> {
+        match self.nodes.get(id)?.kind {
+            Kind::Directory { .. } => Some(DirectoryRef { fs: self, id }),
+            Kind::File => None,
+        }
+    }
+
+    pub fn file(&self, id: NodeId) -> Option<FileRef<'_>> {
+        match self.nodes.get(id)?.kind {
+            Kind::File => Some(FileRef { fs: self, id }),
+            Kind::Directory { .. } => None,
+        }
+    }
+
+    pub fn inode(&self, id: NodeId) -> Option<Inode<'_>> {
+        match self.nodes.get(id)?.kind {
+            Kind::Directory { .. } => Some(Inode::Directory(DirectoryRef { fs: self, id })),
+            Kind::File => Some(Inode::File(FileRef { fs: self, id })),
+        }
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes.get(id)?.parent
+    }
+
+    pub fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        self.children(dir).iter().copied().find(|&child| self.nodes[child].name == name)
+    }
+
+    fn children(&self, dir: NodeId) -> &[NodeId] {
+        match self.nodes.get(dir).map(|node| &node.kind) {
+            Some(Kind::Directory { children }) => children,
+            _ => &[],
+        }
+    }
+
+    // Returns the directory with that name inside `parent`, creating it if it doesn't exist yet
+    pub fn add_directory(&mut self, parent: NodeId, name: &str) -> Result<NodeId, String> {
+        match self.child(parent, name) {
+            Some(id) if self.directory(id).is_some() => Ok(id),
+            Some(_) => Err(format!("{} is a file", self.child_path(parent, name))),
+            None => self.add_node(parent, name, Kind::Directory { children: Vec::new() }, 0),
+        }
+    }
+
+    // Adding a file that already exists with the same size does nothing
+    pub fn add_file(&mut self, parent: NodeId, name: &str, size: u64) -> Result<NodeId, String> {
+        match self.child(parent, name) {
+            Some(id) if self.file(id).is_some_and(|file| file.size() == size) => Ok(id),
+            Some(_) => Err(format!("{} already exists", self.child_path(parent, name))),
+            None => self.add_node(parent, name, Kind::File, size),
+        }
+    }
+
+    fn add_node(&mut self, parent: NodeId, name: &str, kind: Kind, size: u64) -> Result<NodeId, String> {
+        if self.directory(parent).is_none() {
+            return Err(format!("Not a directory: {}", parent));
+        }
+
+        let id = self.nodes.len();
+        self.nodes.push(Node { name: name.to_string(), parent: Some(parent), kind, size });
+        if let Kind::Directory { children } = &mut self.nodes[parent].kind {
+            children.push(id);
+        }
+
+        // New directories are empty, so only files change the sizes of their ancestors
+        let mut ancestor = Some(parent).filter(|_| size > 0);
+        while let Some(dir) = ancestor {
+            self.nodes[dir].size += size;
+            ancestor = self.nodes[dir].parent;
+        }
+
+        Ok(id)
+    }
+
+    // Adds the directory under `parent` together with everything inside it
+    pub fn add_tree(&mut self, parent: NodeId, dir: &Directory) -> Result<NodeId, String> {
+        let id = self.add_directory(parent, &dir.name)?;
+        self.add_entries(id, dir)?;
+
+        Ok(id)
+    }
+
+    fn add_entries(&mut self, id: NodeId, dir: &Directory) -> Result<(), String> {
+        for inode in &dir.inodes {
+            match inode {
+                Entry::Directory(child) => self.add_tree(id, child).map(|_| ())?,
+                Entry::File(file) => self.add_file(id, &file.name, file.size).map(|_| ())?,
+            }
+        }
+
+        Ok(())
+    }
+
+    // Inode of an absolute path like `/a/b/c`
+    pub fn lookup(&self, path: &str) -> Option<NodeId> {
+        let path = path.strip_prefix('/')?;
+
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(Self::ROOT, |dir, name| self.child(dir, name))
+    }
+
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = Vec::new();
+        let mut node = Some(id);
+        while let Some(current) = node.filter(|&current| current != Self::ROOT) {
+            names.push(self.nodes[current].name.as_str());
+            node = self.nodes[current].parent;
+        }
+        names.reverse();
+
+        format!("/{}", names.join("/"))
+    }
+
+    fn child_path(&self, parent: NodeId, name: &str) -> String {
+        match self.path(parent).as_str() {
+            "/" => format!("/{}", name),
+            path => format!("{}/{}", path, name),
+        }
     }
 }
 
-#[derive(Debug)]
+// Directory built on its own, out of any filesystem, by adding files and directories to it. It
+// becomes part of a `FileSystem` with `add_tree`, or is the root of a new one.
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub struct Directory {
     pub name: String,
-    inodes: Vec<Node>,
+    inodes: Vec<Entry>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+enum Entry {
+    Directory(Directory),
+    File(File),
 }
 
 impl Directory {
     pub fn new(name: &str) -> Self {
         Self { name: name.to_string(), inodes: vec![] }
     }
+
     pub fn add_file(&mut self, file: File) {
-        self.inodes.push(Box::new(file));
+        self.inodes.push(Entry::File(file));
     }
+
     pub fn add_directory(&mut self, dir: Directory) {
-        self.inodes.push(Box::new(dir));
+        self.inodes.push(Entry::Directory(dir));
     }
 
     pub fn files(&self) -> Vec<&File> {
         self.inodes
             .iter()
-            .filter(|inode| inode.as_any().type_id() == TypeId::of::<File>())
-            .map(|inode| inode.as_any().downcast_ref::<File>().unwrap())
-            .collect::<Vec<&File>>()
+            .filter_map(|inode| match inode {
+                Entry::File(file) => Some(file),
+                Entry::Directory(_) => None,
+            })
+            .collect()
     }
 
     pub fn directories(&self) -> Vec<&Directory> {
         self.inodes
             .iter()
-            .filter(|inode| inode.as_any().type_id() == TypeId::of::<Directory>())
-            .map(|inode| inode.as_any().downcast_ref::<Directory>().unwrap())
-            .collect::<Vec<&Directory>>()
-    }
-}
-
-impl INode for Directory {
-    fn size(&self) -> u32 {
-        self.inodes.iter().fold(0, |acc, inode| acc + inode.size())
+            .filter_map(|inode| match inode {
+                Entry::Directory(dir) => Some(dir),
+                Entry::File(_) => None,
+            })
+            .collect()
     }
 
-    fn as_any(&self) -> &dyn Any {
-        self
+    pub fn size(&self) -> u64 {
+        self.inodes
+            .iter()
+            .map(|inode| match inode {
+                Entry::Directory(dir) => dir.size(),
+                Entry::File(file) => file.size(),
+            })
+            .sum()
     }
 }
 
-impl PartialEq for Directory {
-    fn eq(&self, other: &Self) -> bool {
-        let mut equals = self.name == other.name;
-        equals &= self.inodes.len() == other.inodes.len();
-        equals &= self.inodes.iter().zip(other.inodes.iter()).filter(|&(a, b)| a != b).count() == 0;
-        equals
+// The name of the directory is ignored, the root of a filesystem is always `/`
+impl TryFrom<&Directory> for FileSystem {
+    type Error = String;
+
+    fn try_from(dir: &Directory) -> Result<Self, Self::Error> {
+        let mut fs = FileSystem::new();
+        fs.add_entries(FileSystem::ROOT, dir)?;
+
+        Ok(fs)
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub struct File {
     name: String,
-    size: u32,
+    size: u64,
 }
 
 impl File {
-    pub fn new(name: &str, size: u32) -> Self {
+    pub fn new(name: &str, size: u64) -> Self {
         Self { name: name.to_string(), size }
     }
-}
 
-impl INode for File {
-    fn size(&self) -> u32 {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> u64 {
         self.size
     }
+}
+
+#[derive(Clone, Copy)]
+pub struct DirectoryRef<'a> {
+    fs: &'a FileSystem,
+    id: NodeId,
+}
+
+impl<'a> DirectoryRef<'a> {
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    pub fn name(&self) -> &'a str {
+        &self.fs.nodes[self.id].name
+    }
+
+    pub fn size(&self) -> u64 {
+        self.fs.nodes[self.id].size
+    }
+
+    pub fn path(&self) -> String {
+        self.fs.path(self.id)
+    }
+
+    pub fn parent(&self) -> Option<DirectoryRef<'a>> {
+        self.fs.parent(self.id).and_then(|parent| self.fs.directory(parent))
+    }
+
+    pub fn files(&self) -> Vec<FileRef<'a>> {
+        self.fs.children(self.id).iter().filter_map(|&child| self.fs.file(child)).collect()
+    }
+
+    pub fn directories(&self) -> Vec<DirectoryRef<'a>> {
+        self.fs.children(self.id).iter().filter_map(|&child| self.fs.directory(child)).collect()
+    }
+
+    // Files and directories in the order they were added
+    pub fn entries(&self) -> Vec<Inode<'a>> {
+        self.fs.children(self.id).iter().filter_map(|&child| self.fs.inode(child)).collect()
+    }
+
+    pub fn file(&self, name: &str) -> Option<FileRef<'a>> {
+        self.fs.child(self.id, name).and_then(|child| self.fs.file(child))
+    }
+
+    pub fn directory(&self, name: &str) -> Option<DirectoryRef<'a>> {
+        self.fs.child(self.id, name).and_then(|child| self.fs.directory(child))
+    }
+
+    // This directory and all the ones inside it, at any depth, parents before their children
+    pub fn walk(&self) -> Vec<DirectoryRef<'a>> {
+        let mut directories = Vec::new();
+        let mut pending = vec![*self];
+        while let Some(dir) = pending.pop() {
+            directories.push(dir);
+            pending.extend(dir.directories().into_iter().rev());
+        }
+
+        directories
+    }
+}
+
+// Views of the same inode of the same filesystem
+impl PartialEq for DirectoryRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.fs, other.fs) && self.id == other.id
+    }
+}
+
+impl Debug for DirectoryRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Directory({})", self.path())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct FileRef<'a> {
+    fs: &'a FileSystem,
+    id: NodeId,
+}
+
+impl<'a> FileRef<'a> {
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    pub fn name(&self) -> &'a str {
+        &self.fs.nodes[self.id].name
+    }
+
+    pub fn size(&self) -> u64 {
+        self.fs.nodes[self.id].size
+    }
+
+    pub fn path(&self) -> String {
+        self.fs.path(self.id)
+    }
+
+    pub fn parent(&self) -> DirectoryRef<'a> {
+        // Only directories have children, so the parent of a file is always one
+        DirectoryRef { fs: self.fs, id: self.fs.nodes[self.id].parent.unwrap_or(FileSystem::ROOT) }
+    }
+}
+
+impl PartialEq for FileRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.fs, other.fs) && self.id == other.id
+    }
+}
+
+impl Debug for FileRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "File({}, {})", self.path(), self.size())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Inode<'a> {
+    Directory(DirectoryRef<'a>),
+    File(FileRef<'a>),
+}
+
+impl<'a> Inode<'a> {
+    pub fn id(&self) -> NodeId {
+        match self {
+            Inode::Directory(dir) => dir.id(),
+            Inode::File(file) => file.id(),
+        }
+    }
+
+    pub fn name(&self) -> &'a str {
+        match self {
+            Inode::Directory(dir) => dir.name(),
+            Inode::File(file) => file.name(),
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        match self {
+            Inode::Directory(dir) => dir.size(),
+            Inode::File(file) => file.size(),
+        }
+    }
 
-    fn as_any(&self) -> &dyn Any {
-        self
+    pub fn path(&self) -> String {
+        match self {
+            Inode::Directory(dir) => dir.path(),
+            Inode::File(file) => file.path(),
+        }
     }
 }
 
@@ -170,4 +492,179 @@ mod tests {
 
         assert_eq!(dir.size(), 132);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn directories_with_the_same_content_are_equal() {
+        let build = |file_size| {
+            let mut dir = Directory::new("/");
+            let mut sub_dir = Directory::new("a");
+            sub_dir.add_file(File::new("foo", file_size));
+            dir.add_directory(sub_dir);
+            dir
+        };
+
+        assert!(build(35) == build(35));
+        assert!(build(35) != build(36));
+    }
+
+    #[test]
+    fn directories_can_be_added_to_a_filesystem() {
+        let mut dir = Directory::new("/");
+        dir.add_file(File::new("foo", 35));
+        let mut a = Directory::new("a");
+        a.add_file(File::new("bar", 64));
+        dir.add_directory(a.clone());
+
+        let mut fs = FileSystem::try_from(&dir).unwrap();
+        assert_eq!(fs.root().size(), dir.size());
+        assert_eq!(fs.lookup("/a/bar").and_then(|id| fs.file(id)).map(|file| file.size()), Some(64));
+
+        let b = fs.add_tree(fs.lookup("/a").unwrap(), &a).unwrap();
+        assert_eq!(fs.path(b), "/a/a");
+        assert_eq!(fs.root().size(), 163);
+
+        a.add_file(File::new("bar", 1));
+        assert_eq!(fs.add_tree(b, &a), Err(String::from("/a/a/a/bar already exists")));
+    }
+
+    #[test]
+    fn directory_can_be_created_in_the_filesystem() {
+        let mut fs = FileSystem::new();
+        assert_eq!(fs.root().name(), "/");
+        assert_eq!(fs.root().size(), 0);
+
+        let a = fs.add_directory(FileSystem::ROOT, "a").unwrap();
+        assert_eq!(fs.directory(a).unwrap().name(), "a");
+        assert_eq!(fs.directory(a).unwrap().size(), 0);
+    }
+
+    #[test]
+    fn file_can_be_created_in_the_filesystem() {
+        let mut fs = FileSystem::new();
+        let foo = fs.add_file(FileSystem::ROOT, "foo", 35).unwrap();
+
+        let file = fs.file(foo).unwrap();
+        assert_eq!(file.name(), "foo");
+        assert_eq!(file.size(), 35);
+    }
+
+    #[test]
+    fn file_can_be_added_to_a_directory_of_the_filesystem() {
+        let mut fs = FileSystem::new();
+        let foo = fs.add_file(FileSystem::ROOT, "foo", 35).unwrap();
+
+        assert_eq!(fs.root().files(), vec![fs.file(foo).unwrap()]);
+        assert_eq!(fs.root().file("foo"), fs.file(foo));
+        assert_eq!(fs.root().entries(), vec![Inode::File(fs.file(foo).unwrap())]);
+        assert_eq!(fs.inode(foo).unwrap().path(), "/foo");
+        assert_eq!(fs.root().directory("foo"), None);
+    }
+
+    #[test]
+    fn directories_can_be_added_to_a_directory_of_the_filesystem() {
+        let mut fs = FileSystem::new();
+        let a = fs.add_directory(FileSystem::ROOT, "a").unwrap();
+
+        assert_eq!(fs.root().directories(), vec![fs.directory(a).unwrap()]);
+        assert_eq!(fs.root().directory("a").unwrap().parent(), Some(fs.root()));
+        assert_eq!(fs.root().parent(), None);
+    }
+
+    #[test]
+    fn adding_an_existing_entry_returns_it() {
+        let mut fs = FileSystem::new();
+        let a = fs.add_directory(FileSystem::ROOT, "a").unwrap();
+        let foo = fs.add_file(a, "foo", 35).unwrap();
+
+        assert_eq!(fs.add_directory(FileSystem::ROOT, "a"), Ok(a));
+        assert_eq!(fs.add_file(a, "foo", 35), Ok(foo));
+        assert_eq!(fs.root().size(), 35);
+
+        assert_eq!(fs.add_file(a, "foo", 36), Err(String::from("/a/foo already exists")));
+        assert_eq!(fs.add_directory(a, "foo"), Err(String::from("/a/foo is a file")));
+        assert!(fs.add_file(foo, "bar", 1).is_err());
+    }
+
+    #[test]
+    fn size_of_directory_with_files_is_kept_up_to_date() {
+        let mut fs = FileSystem::new();
+        fs.add_file(FileSystem::ROOT, "foo", 35).unwrap();
+
+        assert_eq!(fs.root().size(), 35);
+
+        fs.add_file(FileSystem::ROOT, "bar", 64).unwrap();
+
+        assert_eq!(fs.root().size(), 99);
+    }
+
+    #[test]
+    fn size_of_directory_with_files_and_directories_is_kept_up_to_date() {
+        let mut fs = FileSystem::new();
+        fs.add_file(FileSystem::ROOT, "foo", 35).unwrap();
+        fs.add_file(FileSystem::ROOT, "bar", 64).unwrap();
+
+        let a = fs.add_directory(FileSystem::ROOT, "a").unwrap();
+        let b = fs.add_directory(a, "b").unwrap();
+        fs.add_file(b, "foobar", 33).unwrap();
+
+        assert_eq!(fs.root().size(), 132);
+        assert_eq!(fs.directory(a).unwrap().size(), 33);
+    }
+
+    #[test]
+    fn inodes_can_be_found_by_path() {
+        let mut fs = FileSystem::new();
+        let a = fs.add_directory(FileSystem::ROOT, "a").unwrap();
+        let b = fs.add_directory(a, "b").unwrap();
+        let c = fs.add_file(b, "c", 10).unwrap();
+
+        assert_eq!(fs.lookup("/"), Some(FileSystem::ROOT));
+        assert_eq!(fs.lookup("/a"), Some(a));
+        assert_eq!(fs.lookup("/a/b/"), Some(b));
+        assert_eq!(fs.lookup("/a/b/c"), Some(c));
+        assert_eq!(fs.lookup("/a/c"), None);
+        assert_eq!(fs.lookup("/a/b/c/d"), None);
+        assert_eq!(fs.lookup("a/b"), None);
+
+        assert_eq!(fs.path(c), "/a/b/c");
+        assert_eq!(fs.path(FileSystem::ROOT), "/");
+        assert_eq!(fs.file(c).unwrap().parent(), fs.directory(b).unwrap());
+    }
+
+    #[test]
+    fn all_the_directories_can_be_walked() {
+        let mut fs = FileSystem::new();
+        let a = fs.add_directory(FileSystem::ROOT, "a").unwrap();
+        fs.add_directory(a, "b").unwrap();
+        fs.add_directory(FileSystem::ROOT, "c").unwrap();
+
+        let names = fs.root().walk().iter().map(|dir| dir.name()).collect::<Vec<&str>>();
+        assert_eq!(names, vec!["/", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn filesystems_with_the_same_content_are_equal() {
+        let build = |file_size| {
+            let mut fs = FileSystem::new();
+            let a = fs.add_directory(FileSystem::ROOT, "a").unwrap();
+            fs.add_file(a, "foo", file_size).unwrap();
+            fs
+        };
+
+        assert!(build(35) == build(35));
+        assert!(build(35) != build(36));
+    }
+
+    #[test]
+    fn deep_trees_do_not_overflow_the_stack() {
+        let mut fs = FileSystem::new();
+        let mut dir = FileSystem::ROOT;
+        for _ in 0..100_000 {
+            dir = fs.add_directory(dir, "a").unwrap();
+        }
+        fs.add_file(dir, "foo", 1).unwrap();
+
+        assert_eq!(fs.root().walk().len(), 100_001);
+        assert_eq!(fs.root().size(), 1);
+    }
+}
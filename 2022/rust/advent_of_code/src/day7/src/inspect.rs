@@ -0,0 +1,260 @@
+use std::ops::{Bound, RangeBounds};
+use std::str::FromStr;
+
+use crate::inode::{DirectoryRef, Inode};
+
+// Drawing of the directory in the same format as the puzzle statement, with the sizes of the
+// directories too. Entries are sorted by name.
+pub fn tree(dir: DirectoryRef) -> String {
+    let mut lines = Vec::new();
+    let mut pending = vec![(Inode::Directory(dir), 0)];
+    while let Some((inode, depth)) = pending.pop() {
+        let indent = "  ".repeat(depth);
+        match inode {
+            Inode::Directory(dir) => {
+                lines.push(format!("{}- {} (dir, size={})", indent, dir.name(), dir.size()));
+
+                let mut entries = dir.entries();
+                entries.sort_by_key(|entry| entry.name());
+                pending.extend(entries.into_iter().rev().map(|entry| (entry, depth + 1)));
+            }
+            Inode::File(file) => lines.push(format!("{}- {} (file, size={})", indent, file.name(), file.size())),
+        }
+    }
+
+    lines.join("\n")
+}
+
+// The directory and all the ones inside it, biggest first, and by path when they have the same size
+pub fn disk_usage(dir: DirectoryRef) -> Vec<DirectoryRef> {
+    let mut directories = dir.walk();
+    directories.sort_by_cached_key(|dir| (std::cmp::Reverse(dir.size()), dir.path()));
+
+    directories
+}
+
+// One line per directory with its size and its path, like `du`
+pub fn du(dir: DirectoryRef) -> String {
+    let directories = disk_usage(dir);
+    let width = directories.iter().map(|dir| dir.size().to_string().len()).max().unwrap_or(0);
+
+    directories
+        .iter()
+        .map(|dir| format!("{:>width$}  {}", dir.size(), dir.path()))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Sizes written like Rust ranges: `100..200`, `100..=200`, `100..`, `..200`, `..=200` or just `100`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SizeRange {
+    start: Bound<u64>,
+    end: Bound<u64>,
+}
+
+impl SizeRange {
+    pub fn contains(&self, size: u64) -> bool {
+        (self.start, self.end).contains(&size)
+    }
+}
+
+impl FromStr for SizeRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |size: &str| size.parse::<u64>().map_err(|_| format!("Invalid size range: {}", s));
+
+        let (start, end) = if let Some((start, end)) = s.split_once("..=") {
+            (start, Bound::Included(parse(end)?))
+        } else if let Some((start, end)) = s.split_once("..") {
+            (start, if end.is_empty() { Bound::Unbounded } else { Bound::Excluded(parse(end)?) })
+        } else {
+            (s, Bound::Included(parse(s)?))
+        };
+        let start = if start.is_empty() { Bound::Unbounded } else { Bound::Included(parse(start)?) };
+
+        Ok(Self { start, end })
+    }
+}
+
+// Conditions of `find`, all the given ones must hold
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Query {
+    pub name: Option<String>,
+    pub size: Option<SizeRange>,
+}
+
+impl Query {
+    pub fn matches(&self, inode: &Inode) -> bool {
+        self.name.as_ref().is_none_or(|pattern| glob_matches(pattern, inode.name()))
+            && self.size.is_none_or(|range| range.contains(inode.size()))
+    }
+}
+
+// Files and directories inside the directory, itself included, that match the query. Every
+// directory comes before its files, and these before its subdirectories.
+pub fn find<'a>(dir: DirectoryRef<'a>, query: &Query) -> Vec<Inode<'a>> {
+    dir.walk()
+        .into_iter()
+        .flat_map(|dir| {
+            let mut inodes = vec![Inode::Directory(dir)];
+            inodes.extend(dir.files().into_iter().map(Inode::File));
+            inodes
+        })
+        .filter(|inode| query.matches(inode))
+        .collect()
+}
+
+// Shell pattern where `*` matches any sequence of characters and `?` any single character
+pub fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<char>>();
+    let name = name.chars().collect::<Vec<char>>();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` seen and of the character of the name it matched up to
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                // Let the last `*` take one more character and try again
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cli;
+
+    const SESSION: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    fn paths(inodes: &[Inode]) -> Vec<String> {
+        inodes.iter().map(|inode| inode.path()).collect()
+    }
+
+    #[test]
+    fn tree_is_drawn_like_in_the_puzzle() {
+        let cli = Cli::replay(SESSION).unwrap();
+
+        let expected = "\
+- / (dir, size=48381165)
+  - a (dir, size=94853)
+    - e (dir, size=584)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir, size=24933642)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)";
+        assert_eq!(tree(cli.root()), expected);
+        assert_eq!(tree(cli.root().directory("a").unwrap().directory("e").unwrap()), "- e (dir, size=584)\n  - i (file, size=584)");
+    }
+
+    #[test]
+    fn disk_usage_is_sorted_by_size() {
+        let cli = Cli::replay("$ cd /\n$ ls\ndir b\ndir a\ndir c\n$ cd a\n$ ls\n10 x\n$ cd ..\n$ cd b\n$ ls\n10 x\n$ cd ..\n$ cd c\n$ ls\n30 x").unwrap();
+
+        let directories = disk_usage(cli.root()).iter().map(|dir| dir.path()).collect::<Vec<String>>();
+        assert_eq!(directories, vec!["/", "/c", "/a", "/b"]);
+        assert_eq!(du(cli.root()), "50  /\n30  /c\n10  /a\n10  /b");
+
+        let cli = Cli::replay(SESSION).unwrap();
+        assert_eq!(du(cli.root()), "48381165  /\n24933642  /d\n   94853  /a\n     584  /a/e");
+    }
+
+    #[test]
+    fn size_ranges_are_parsed() {
+        let range = |s: &str| s.parse::<SizeRange>().unwrap();
+
+        assert!(range("100..200").contains(100));
+        assert!(!range("100..200").contains(200));
+        assert!(range("100..=200").contains(200));
+        assert!(!range("100..=200").contains(99));
+        assert!(range("100..").contains(u64::MAX));
+        assert!(range("..200").contains(0));
+        assert!(!range("..=200").contains(201));
+        assert!(range("100").contains(100));
+        assert!(!range("100").contains(101));
+        assert!(range("..").contains(0));
+
+        for invalid in ["", "a..b", "..=", "1...2", "-1..2"] {
+            assert_eq!(invalid.parse::<SizeRange>(), Err(format!("Invalid size range: {}", invalid)));
+        }
+    }
+
+    #[test]
+    fn globs_are_matched() {
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("*", "h.lst"));
+        assert!(glob_matches("*.lst", "h.lst"));
+        assert!(glob_matches("d.*", "d.ext"));
+        assert!(glob_matches("?.???", "d.log"));
+        assert!(glob_matches("*a*b*", "xaxxbx"));
+        assert!(glob_matches("a*b", "abab"));
+        assert!(!glob_matches("a*b", "abba"));
+        assert!(!glob_matches("*.lst", "h.ls"));
+        assert!(!glob_matches("?", ""));
+        assert!(!glob_matches("d", "d.log"));
+    }
+
+    #[test]
+    fn inodes_are_found_by_name_and_size() {
+        let cli = Cli::replay(SESSION).unwrap();
+
+        let by_name = Query { name: Some(String::from("d*")), size: None };
+        assert_eq!(paths(&find(cli.root(), &by_name)), vec!["/d", "/d/d.log", "/d/d.ext"]);
+
+        let by_size = Query { name: None, size: Some("..=100000".parse().unwrap()) };
+        assert_eq!(paths(&find(cli.root(), &by_size)), vec!["/a", "/a/f", "/a/g", "/a/h.lst", "/a/e", "/a/e/i"]);
+
+        let both = Query { name: Some(String::from("?")), size: Some("5000..".parse().unwrap()) };
+        assert_eq!(paths(&find(cli.root(), &both)), vec!["/", "/a", "/a/f", "/d", "/d/j", "/d/k"]);
+
+        assert_eq!(find(cli.root(), &Query::default()).len(), 14);
+        assert_eq!(paths(&find(cli.root().directory("a").unwrap(), &by_name)), Vec::<String>::new());
+    }
+}
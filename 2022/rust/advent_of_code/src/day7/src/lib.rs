@@ -0,0 +1,305 @@
+#![deny(unused)]
+
+pub mod inode;
+pub mod inspect;
+
+use aoc_common::{lines, read_input, Answer, Day, Part};
+use crate::inode::{DirectoryRef, FileSystem, NodeId};
+
+pub const DAY: Day = Day {
+    number: 7,
+    title: "No Space Left On Device",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Sum of the sizes of the directories of at most 100000", solve: part_one },
+        Part { description: "Size of the smallest directory to delete", solve: part_two },
+    ],
+};
+
+const SMALL_DIRECTORY_SIZE: u64 = 100_000;
+const DISK_SIZE: u64 = 70_000_000;
+const SPACE_NEEDED_FOR_UPDATE: u64 = 30_000_000;
+
+#[derive(PartialEq, Eq, Debug)]
+enum Command {
+    Cd(String),
+    Ls,
+    Unknown(String),
+}
+
+impl From<&str> for Command {
+    fn from(input: &str) -> Self {
+        let c = input.replace("$ ", "");
+        let mut split = c.split(' ');
+
+        match (split.next(), split.next()) {
+            (Some("cd"), Some(target)) => Command::Cd(target.to_string()),
+            (Some("ls"), None) => Command::Ls,
+            _ => Command::Unknown(c.to_string())
+        }
+    }
+}
+
+pub struct Cli {
+    fs: FileSystem,
+    // None until the first `cd /`
+    current_dir: Option<NodeId>,
+    // The lines that don't start with `$` are the output of `ls`
+    listing: bool,
+}
+
+impl Default for Cli {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cli {
+    pub fn new() -> Self {
+        Self { fs: FileSystem::new(), current_dir: None, listing: false }
+    }
+
+    // Builds the filesystem seen in a terminal session
+    pub fn replay(session: &str) -> Result<Self, String> {
+        let mut cli = Cli::new();
+
+        for (number, line) in (1..).zip(lines(session)) {
+            cli.parse_line(line).map_err(|error| format!("Line {}: {}", number, error))?;
+        }
+
+        Ok(cli)
+    }
+
+    pub fn filesystem(&self) -> &FileSystem {
+        &self.fs
+    }
+
+    pub fn root(&self) -> DirectoryRef<'_> {
+        self.fs.root()
+    }
+
+    pub fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        if line.starts_with("$ ") {
+            self.listing = false;
+            self.parse_command(line)
+        } else {
+            self.parse_listing(line)
+        }
+    }
+
+    pub fn parse_command(&mut self, input: &str) -> Result<(), String> {
+        let cmd = Command::from(input);
+
+        match cmd {
+            Command::Cd(target) => {
+                if target == "/" {
+                    self.current_dir = Some(FileSystem::ROOT);
+                    return Ok(());
+                }
+
+                let current_dir = self.current_dir()?;
+                self.current_dir = Some(match target.as_str() {
+                    // Like in a shell, the parent of the root is the root itself
+                    ".." => self.fs.parent(current_dir).unwrap_or(FileSystem::ROOT),
+                    // The directory is created if it wasn't listed before
+                    _ => self.fs.add_directory(current_dir, &target)?,
+                });
+            }
+            Command::Ls => {
+                self.current_dir()?;
+                self.listing = true;
+            }
+            Command::Unknown(cmd) => return Err(format!("Unknown command: {}", cmd)),
+        }
+
+        Ok(())
+    }
+
+    // An entry of the current directory, `dir <name>` or `<size> <name>`. Entries already known are
+    // kept as they are, so listing a directory twice doesn't duplicate them, but an entry that
+    // doesn't match the known one is an error.
+    fn parse_listing(&mut self, line: &str) -> Result<(), String> {
+        if !self.listing {
+            return Err(format!("Output without a command: {}", line));
+        }
+
+        let current_dir = self.current_dir()?;
+        match line.split_once(' ') {
+            Some(("dir", name)) => self.fs.add_directory(current_dir, name).map(|_| ()),
+            Some((size, name)) => {
+                let size = size.parse::<u64>().map_err(|_| format!("Invalid file size: {}", line))?;
+                self.fs.add_file(current_dir, name, size).map(|_| ())
+            }
+            None => Err(format!("Invalid listing: {}", line)),
+        }
+    }
+
+    fn current_dir(&self) -> Result<NodeId, String> {
+        self.current_dir.ok_or_else(|| String::from("No current directory, the session must start with `cd /`"))
+    }
+
+    pub fn pwd(&self) -> String {
+        self.current_dir.map(|dir| self.fs.path(dir)).unwrap_or_default()
+    }
+
+    // Entries of the current directory in the same format as the session, directories first
+    pub fn ls(&self) -> String {
+        let Some(dir) = self.current_dir.and_then(|dir| self.fs.directory(dir)) else {
+            return String::new();
+        };
+
+        let mut entries = dir.directories().iter().map(|d| format!("dir {}", d.name())).collect::<Vec<String>>();
+        entries.extend(dir.files().iter().map(|f| format!("{} {}", f.size(), f.name())));
+        entries.join("\n")
+    }
+}
+
+pub fn part_one(input: &str) -> Answer {
+    let cli = Cli::replay(input)?;
+    let root = cli.root();
+
+    let total = root
+        .walk()
+        .iter()
+        .map(|dir| dir.size())
+        .filter(|&size| size <= SMALL_DIRECTORY_SIZE)
+        .sum::<u64>();
+
+    Ok(total.to_string())
+}
+
+pub fn part_two(input: &str) -> Answer {
+    let cli = Cli::replay(input)?;
+    let root = cli.root();
+
+    let free = DISK_SIZE.checked_sub(root.size()).ok_or("The files don't fit in the disk")?;
+    let to_free = SPACE_NEEDED_FOR_UPDATE.saturating_sub(free);
+
+    root.walk()
+        .iter()
+        .map(|dir| dir.size())
+        .filter(|&size| size >= to_free)
+        .min()
+        .map(|size| size.to_string())
+        .ok_or_else(|| String::from("No directory is big enough"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSION: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    #[test]
+    fn cd_command_is_identified() {
+        assert_eq!(Command::from("$ cd target"), Command::Cd(String::from("target")))
+    }
+
+    #[test]
+    fn ls_command_is_identified() {
+        assert_eq!(Command::from("$ ls"), Command::Ls)
+    }
+
+    #[test]
+    fn unknown_commands_are_identified() {
+        assert_eq!(Command::from("$ cd"), Command::Unknown(String::from("cd")));
+        assert_eq!(Command::from("$ rm -rf"), Command::Unknown(String::from("rm -rf")));
+    }
+
+    #[test]
+    fn cd_command_creates_the_dir_and_changes_the_fs_current_dir() {
+        let mut cli = Cli::new();
+
+        cli.parse_command("$ cd /").unwrap();
+
+        assert_eq!(cli.pwd(), "/");
+        assert_eq!(cli.ls(), "");
+
+        cli.parse_command("$ cd a").unwrap();
+        cli.parse_command("$ cd b").unwrap();
+
+        assert_eq!(cli.pwd(), "/a/b");
+        let b = cli.root().directory("a").unwrap().directory("b").unwrap();
+        assert_eq!(b.path(), "/a/b");
+        assert_eq!(cli.filesystem().lookup("/a/b"), Some(b.id()));
+    }
+
+    #[test]
+    fn cd_command_goes_back_to_the_parent_and_the_root() {
+        let mut cli = Cli::new();
+
+        for command in ["$ cd /", "$ cd a", "$ cd b", "$ cd .."] {
+            cli.parse_command(command).unwrap();
+        }
+        assert_eq!(cli.pwd(), "/a");
+
+        cli.parse_command("$ cd /").unwrap();
+        assert_eq!(cli.pwd(), "/");
+
+        cli.parse_command("$ cd ..").unwrap();
+        assert_eq!(cli.pwd(), "/");
+    }
+
+    #[test]
+    fn ls_output_fills_the_current_dir() {
+        let cli = Cli::replay(SESSION).unwrap();
+        let root = cli.root();
+
+        assert_eq!(root.size(), 48381165);
+        assert_eq!(root.directory("a").unwrap().size(), 94853);
+        assert_eq!(root.directory("a").unwrap().directory("e").unwrap().size(), 584);
+        assert_eq!(root.directory("d").unwrap().size(), 24933642);
+        assert_eq!(root.walk().len(), 4);
+    }
+
+    #[test]
+    fn listing_a_directory_twice_does_not_duplicate_its_entries() {
+        let cli = Cli::replay("$ cd /\n$ ls\ndir a\n10 b\n$ ls\ndir a\n10 b").unwrap();
+
+        assert_eq!(cli.ls(), "dir a\n10 b");
+        assert_eq!(cli.root().size(), 10);
+    }
+
+    #[test]
+    fn listed_entries_must_match_the_known_ones() {
+        assert_eq!(Cli::replay("$ cd /\n$ ls\ndir a\n10 a").err(), Some(String::from("Line 4: /a already exists")));
+        assert_eq!(Cli::replay("$ cd /\n$ ls\n10 b\n$ ls\n20 b").err(), Some(String::from("Line 5: /b already exists")));
+    }
+
+    #[test]
+    fn invalid_sessions_are_reported() {
+        assert_eq!(Cli::replay("$ ls").err(), Some(String::from("Line 1: No current directory, the session must start with `cd /`")));
+        assert!(Cli::replay("$ cd /\n10 a").is_err());
+        assert!(Cli::replay("$ cd /\n$ ls\nten a").is_err());
+        assert!(Cli::replay("$ cd /\n$ ls\nfoo").is_err());
+        assert!(Cli::replay("$ cd /\n$ mkdir a").is_err());
+    }
+
+    #[test]
+    fn both_parts_are_solved_for_the_example() {
+        assert_eq!(part_one(SESSION), Ok(String::from("95437")));
+        assert_eq!(part_two(SESSION), Ok(String::from("24933642")));
+    }
+}
@@ -1,110 +1,54 @@
-// #![deny(unused)]
+use std::env;
+use std::process;
 
-mod inode;
+use aoc_common::read_input;
+use day7::inspect::{du, find, tree, Query};
+use day7::Cli;
 
-use std::cell::RefCell;
-use std::fs;
-use std::ops::Deref;
-use std::rc::Rc;
-use crate::inode::Directory;
+const USAGE: &str = "Usage: day7 [tree | du | find [--name <glob>] [--size <range>]]";
 
-#[derive(PartialEq, Eq, Debug)]
-enum Command {
-    Cd(String),
-    Ls,
-    Unknown(String),
-}
-
-impl From<&str> for Command {
-    fn from(input: &str) -> Self {
-        let c = input.replace("$ ", "");
-        let mut split = c.split(' ');
-
-        match split.next().unwrap() {
-            "cd" => Command::Cd(split.next().unwrap().to_string()),
-            "ls" => Command::Ls,
-            cmd => Command::Unknown(cmd.to_string())
-        }
-    }
-}
-
-struct Cli {
-    root_dir: Rc<RefCell<Directory>>,
-    current_dir: Option<Rc<RefCell<Directory>>>,
-}
-
-impl Cli {
-    pub fn new() -> Self {
-        Self {
-            root_dir: Rc::new(RefCell::new(Directory::new("/"))),
-            current_dir: None,
-        }
-    }
-
-    pub fn parse_command(&mut self, input: &str) {
-        let cmd = Command::from(input);
-
-        match cmd {
-            Command::Cd(target) => {
-                if target == "/" {
-                    self.current_dir = Some(Rc::clone(&self.root_dir))
-                }
-
-                // TODO: Check if the dir already exists, if not insert it
-                //  In any case, update the current_dir
-
-                // println!("{:?}", dir)
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    match args.iter().map(String::as_str).collect::<Vec<&str>>().as_slice() {
+        [] => aoc_common::run_day(&day7::DAY),
+        ["tree"] => println!("{}", tree(replay().root())),
+        ["du"] => println!("{}", du(replay().root())),
+        ["find", options @ ..] => {
+            let query = parse_query(options).unwrap_or_else(|error| {
+                eprintln!("{}\n{}", error, USAGE);
+                process::exit(2);
+            });
+
+            let cli = replay();
+            for inode in find(cli.root(), &query) {
+                println!("{}", inode.path());
             }
-            Command::Ls => {}
-            Command::Unknown(_) => {}
         }
-    }
-
-    pub fn pwd(&self) -> String {
-        if let Some(cur) = &self.current_dir {
-            cur.borrow().name.to_string()
-        } else {
-            String::new()
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
         }
     }
-
-    pub fn ls(&self) -> String {
-        String::new()
-    }
 }
 
-fn main() {
-    let input = fs::read_to_string("src/day7/input/input.txt")
-        .expect("Cannot open input file");
-
-    let lines = input.split('\n').map(|line| line.to_string()).collect::<Vec<String>>();
-
-    for (index, line) in lines.iter().enumerate() {
-        println!("{}. {}", index, line)
-    }
+fn replay() -> Cli {
+    Cli::replay(&read_input!()).unwrap_or_else(|error| {
+        eprintln!("Cannot replay the session: {}", error);
+        process::exit(1);
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn cd_command_is_identified() {
-        assert_eq!(Command::from("$ cd target"), Command::Cd(String::from("target")))
-    }
+fn parse_query(options: &[&str]) -> Result<Query, String> {
+    let mut query = Query::default();
 
-    #[test]
-    fn ls_command_is_identified() {
-        assert_eq!(Command::from("$ ls"), Command::Ls)
+    for option in options.chunks(2) {
+        match option {
+            ["--name", glob] => query.name = Some(glob.to_string()),
+            ["--size", range] => query.size = Some(range.parse()?),
+            _ => return Err(format!("Invalid option: {}", option.join(" "))),
+        }
     }
 
-    #[test]
-    fn cd_command_creates_the_dir_and_changes_the_fs_current_dir() {
-        let mut cli = Cli::new();
-
-        cli.parse_command("$ cd /");
-
-        assert_eq!(cli.pwd(), "/");
-        assert_eq!(cli.ls(), "");
-    }
+    Ok(query)
 }
@@ -0,0 +1,35 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use day8::generated_forest;
+
+const SIZE: usize = 2000;
+// The methods checking every tree on its own are cubic, so they are compared on a smaller forest
+const SMALL_SIZE: usize = 200;
+
+fn bench<T>(name: &str, runs: u32, f: impl Fn() -> T) {
+    let mut best = Duration::MAX;
+    for _ in 0..runs {
+        let start = Instant::now();
+        black_box(f());
+        best = best.min(start.elapsed());
+    }
+
+    println!("{:<40} {:>10.3} ms", name, best.as_secs_f64() * 1000.0);
+}
+
+fn main() {
+    let forest = generated_forest(SIZE, SIZE);
+    bench(&format!("visibility_map {}x{}", SIZE, SIZE), 10, || forest.visibility_map());
+    bench(&format!("scenic_scores {}x{}", SIZE, SIZE), 10, || forest.scenic_scores());
+
+    let forest = generated_forest(SMALL_SIZE, SMALL_SIZE);
+    bench(&format!("visibility_map {}x{}", SMALL_SIZE, SMALL_SIZE), 10, || forest.visibility_map());
+    bench(&format!("get_number_of_visible_trees {}x{}", SMALL_SIZE, SMALL_SIZE), 3, || {
+        forest.get_number_of_visible_trees()
+    });
+    bench(&format!("scenic_scores {}x{}", SMALL_SIZE, SMALL_SIZE), 10, || forest.scenic_scores());
+    bench(&format!("get_best_position_to_place_the_camp {}x{}", SMALL_SIZE, SMALL_SIZE), 3, || {
+        forest.get_best_position_to_place_the_camp()
+    });
+}
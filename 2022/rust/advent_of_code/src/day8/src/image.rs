@@ -0,0 +1,196 @@
+use crate::{best_score, Forest};
+
+pub type Rgb = [u8; 3];
+
+const VISIBLE: Rgb = [40, 160, 40];
+const HIDDEN: Rgb = [20, 30, 20];
+// Not in the colors of the heat scale, so the best camp stands out in the heatmaps and the scenic
+// scores PPM
+const BEST_CAMP: Rgb = [0, 220, 255];
+// The visibility PGM only has black and white trees, so the best camp is the only grey one
+const BEST_CAMP_GREY: u8 = 128;
+
+impl Forest {
+    // Binary PGM with the visible trees in white, the hidden ones in black and the best camp in grey
+    pub fn visibility_pgm(&self) -> Vec<u8> {
+        let mut pixels = self
+            .visibility_map()
+            .iter()
+            .map(|row| row.iter().map(|&visible| if visible { 255 } else { 0 }).collect())
+            .collect::<Vec<Vec<u8>>>();
+
+        if let Some(((i, j), _)) = self.best_camp() {
+            pixels[i][j] = BEST_CAMP_GREY;
+        }
+
+        pgm(&pixels)
+    }
+
+    // Binary PPM with the scenic scores in a heat scale and the best camp highlighted
+    pub fn scenic_scores_ppm(&self) -> Vec<u8> {
+        ppm(&self.scenic_score_colors())
+    }
+
+    // Terminal drawing with a pair of colored cells per tree, the best camp highlighted
+    pub fn visibility_heatmap(&self) -> String {
+        let mut colors = self
+            .visibility_map()
+            .iter()
+            .map(|row| row.iter().map(|&visible| if visible { VISIBLE } else { HIDDEN }).collect())
+            .collect::<Vec<Vec<Rgb>>>();
+
+        if let Some(((i, j), _)) = self.best_camp() {
+            colors[i][j] = BEST_CAMP;
+        }
+
+        ansi(&colors)
+    }
+
+    pub fn scenic_scores_heatmap(&self) -> String {
+        ansi(&self.scenic_score_colors())
+    }
+
+    fn scenic_score_colors(&self) -> Vec<Vec<Rgb>> {
+        let scores = self.scenic_scores();
+        let best = best_score(&scores);
+        let max = best.map_or(0, |(_, score)| score);
+
+        let mut colors = scores
+            .iter()
+            .map(|row| row.iter().map(|&score| heat(scale(score, max))).collect())
+            .collect::<Vec<Vec<Rgb>>>();
+
+        if let Some(((i, j), _)) = best {
+            colors[i][j] = BEST_CAMP;
+        }
+
+        colors
+    }
+}
+
+// Scores are products of four distances, so a few trees have scores much higher than the rest and
+// a logarithmic scale shows more than a linear one
+fn scale(score: u64, max: u64) -> f64 {
+    if max == 0 {
+        return 0.0;
+    }
+
+    (score as f64).ln_1p() / (max as f64).ln_1p()
+}
+
+// From black to red, yellow and white as `t` goes from 0 to 1
+pub fn heat(t: f64) -> Rgb {
+    let channel = |offset: f64| ((3.0 * t - offset).clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    [channel(0.0), channel(1.0), channel(2.0)]
+}
+
+pub fn pgm(pixels: &[Vec<u8>]) -> Vec<u8> {
+    let mut image = header("P5", pixels);
+    image.extend(pixels.iter().flatten());
+
+    image
+}
+
+pub fn ppm(pixels: &[Vec<Rgb>]) -> Vec<u8> {
+    let mut image = header("P6", pixels);
+    image.extend(pixels.iter().flatten().flatten());
+
+    image
+}
+
+fn header<T>(magic: &str, pixels: &[Vec<T>]) -> Vec<u8> {
+    let width = pixels.first().map_or(0, |row| row.len());
+
+    format!("{}\n{} {}\n255\n", magic, width, pixels.len()).into_bytes()
+}
+
+// Every pixel is drawn as two spaces with a 24-bit background color, so that it is about square
+pub fn ansi(pixels: &[Vec<Rgb>]) -> String {
+    pixels
+        .iter()
+        .map(|row| {
+            let cells = row.iter().map(|[r, g, b]| format!("\x1b[48;2;{};{};{}m  ", r, g, b)).collect::<String>();
+            format!("{}\x1b[0m", cells)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOREST: &str = "30373\n25512\n65332\n33549\n35390";
+
+    #[test]
+    fn visibility_is_exported_as_pgm_with_the_best_camp() {
+        let image = Forest::from(FOREST).visibility_pgm();
+
+        let header = b"P5\n5 5\n255\n";
+        assert_eq!(&image[..header.len()], header);
+
+        #[rustfmt::skip]
+        let expected = [
+            255, 255, 255, 255, 255,
+            255, 255, 255,   0, 255,
+            255, 255,   0, 255, 255,
+            255,   0, 128,   0, 255,
+            255, 255, 255, 255, 255,
+        ];
+        assert_eq!(&image[header.len()..], expected);
+        assert_eq!(image[header.len() + 3 * 5 + 2], BEST_CAMP_GREY);
+    }
+
+    #[test]
+    fn scenic_scores_are_exported_as_ppm_with_the_best_camp() {
+        let image = Forest::from(FOREST).scenic_scores_ppm();
+
+        let header = b"P6\n5 5\n255\n";
+        assert_eq!(&image[..header.len()], header);
+
+        let pixels = image[header.len()..].chunks(3).collect::<Vec<&[u8]>>();
+        assert_eq!(pixels.len(), 25);
+        // Trees on the edge have a score of 0
+        assert_eq!(pixels[0], [0, 0, 0]);
+        assert_eq!(pixels[3 * 5 + 2], BEST_CAMP);
+        // The score of the tree at (1, 2) is 4 and the best one is 8
+        assert_eq!(pixels[5 + 2], heat(5f64.ln() / 9f64.ln()));
+    }
+
+    #[test]
+    fn heat_scale_goes_from_black_to_white() {
+        assert_eq!(heat(0.0), [0, 0, 0]);
+        assert_eq!(heat(1.0 / 3.0), [255, 0, 0]);
+        assert_eq!(heat(2.0 / 3.0), [255, 255, 0]);
+        assert_eq!(heat(1.0), [255, 255, 255]);
+    }
+
+    #[test]
+    fn heatmaps_use_a_colored_cell_per_tree() {
+        let forest = Forest::from(FOREST);
+
+        for heatmap in [forest.visibility_heatmap(), forest.scenic_scores_heatmap()] {
+            let rows = heatmap.lines().collect::<Vec<&str>>();
+            assert_eq!(rows.len(), 5);
+            assert!(rows.iter().all(|row| row.matches("\x1b[48;2;").count() == 5 && row.ends_with("\x1b[0m")));
+            assert_eq!(heatmap.matches("\x1b[48;2;0;220;255m  ").count(), 1);
+        }
+
+        let visibility = forest.visibility_heatmap();
+        assert_eq!(visibility.matches("\x1b[48;2;20;30;20m  ").count(), 4);
+        assert_eq!(
+            visibility.lines().nth(3),
+            Some("\x1b[48;2;40;160;40m  \x1b[48;2;20;30;20m  \x1b[48;2;0;220;255m  \x1b[48;2;20;30;20m  \x1b[48;2;40;160;40m  \x1b[0m")
+        );
+    }
+
+    #[test]
+    fn empty_forests_are_exported() {
+        let forest = Forest::from("");
+
+        assert_eq!(forest.visibility_pgm(), b"P5\n0 0\n255\n");
+        assert_eq!(forest.scenic_scores_ppm(), b"P6\n0 0\n255\n");
+        assert_eq!(forest.scenic_scores_heatmap(), "");
+    }
+}
@@ -0,0 +1,376 @@
+#![deny(unused)]
+
+pub mod image;
+
+use aoc_common::{lines, read_input, Answer, Day, Part};
+
+pub const DAY: Day = Day {
+    number: 8,
+    title: "Treetop Tree House",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Trees visible from outside the grid", solve: part_one },
+        Part { description: "Highest scenic score possible for any tree", solve: part_two },
+    ],
+};
+
+pub struct Forest {
+    trees: Vec<Vec<u32>>,
+}
+
+impl Forest {
+    pub fn height(&self) -> usize {
+        self.trees.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.trees.first().map_or(0, |row| row.len())
+    }
+
+    pub fn tree_hight(&self, pos: (usize, usize)) -> u32 {
+        self.trees[pos.0][pos.1]
+    }
+
+    pub fn tree_is_visible(&self, pos: (usize, usize)) -> bool {
+        if pos.0 == 0 || pos.0 == self.trees.len() - 1
+            || pos.1 == 0 || pos.1 == self.trees[0].len() - 1 {
+            return true;
+        }
+
+        let mut visible = false;
+
+        // Look left
+        visible |= self.get_trees_from_left(pos).iter().all(|&&a| a < self.tree_hight(pos));
+        // Look right
+        visible |= self.get_trees_from_right(pos).iter().all(|&&a| a < self.tree_hight(pos));
+        // Look up
+        visible |= self.get_trees_from_above(pos).iter().all(|&&a| a < self.tree_hight(pos));
+        // Look down
+        visible |= self.get_trees_from_below(pos).iter().all(|&&a| a < self.tree_hight(pos));
+
+        visible
+    }
+
+    pub fn get_number_of_visible_trees(&self) -> u32 {
+        let mut visible_trees = 0u32;
+        for i in 0..self.trees.len() {
+            for j in 0..self.trees[0].len() {
+                if self.tree_is_visible((i, j)) {
+                    visible_trees += 1;
+                }
+            }
+        }
+        visible_trees
+    }
+
+    pub fn get_visibility_from_tree(&self, pos: (usize, usize)) -> u32 {
+        let mut visibility = 1u32;
+        let mut count = 0u32;
+
+        // Left
+        let mut trees = self.get_trees_from_left(pos);
+        trees.reverse();
+        for &tree in trees {
+            count += 1;
+            if tree >= self.tree_hight(pos) { break; }
+        }
+        visibility *= count;
+        count = 0;
+
+        // Right
+        trees = self.get_trees_from_right(pos);
+        for &tree in trees {
+            count += 1;
+            if tree >= self.tree_hight(pos) { break; }
+        }
+        visibility *= count;
+        count = 0;
+
+        // Up
+        trees = self.get_trees_from_above(pos);
+        trees.reverse();
+        for &tree in trees {
+            count += 1;
+            if tree >= self.tree_hight(pos) { break; }
+        }
+        visibility *= count;
+        count = 0;
+
+        // Down
+        trees = self.get_trees_from_below(pos);
+        // trees.reverse();
+        for &tree in trees {
+            count += 1;
+            if tree >= self.tree_hight(pos) { break; }
+        }
+        visibility *= count;
+
+        visibility
+    }
+
+    pub fn get_best_position_to_place_the_camp(&self) -> u32 {
+        let mut max_visibility = 0u32;
+        for i in 0..self.trees.len() {
+            for j in 0..self.trees[0].len() {
+                let visibility = self.get_visibility_from_tree((i, j));
+                if visibility > max_visibility {
+                    max_visibility = visibility
+                }
+            }
+        }
+        max_visibility
+    }
+
+    // Whether every tree is visible from outside the grid, sweeping every row and column once from
+    // both ends while keeping the tallest tree seen so far
+    pub fn visibility_map(&self) -> Vec<Vec<bool>> {
+        let mut visible = vec![vec![false; self.width()]; self.height()];
+
+        self.for_each_ray(|ray| {
+            let mut tallest: Option<u32> = None;
+            for (i, j) in ray {
+                let height = self.trees[i][j];
+                if tallest.is_none_or(|tallest| height > tallest) {
+                    visible[i][j] = true;
+                    tallest = Some(height);
+                }
+            }
+        });
+
+        visible
+    }
+
+    // Scenic score of every tree. The viewing distance in each direction is the distance to the
+    // closest tree at least as tall, found with a stack of the trees that can still block the view
+    // of the next ones, which are kept in strictly decreasing order of height. Scores are products
+    // of four distances, which overflow a u32 in forests wider than about 500 trees.
+    pub fn scenic_scores(&self) -> Vec<Vec<u64>> {
+        let mut scores = vec![vec![1u64; self.width()]; self.height()];
+        // Position in the ray and height of the trees that can block the view
+        let mut blocking: Vec<(usize, u32)> = Vec::new();
+
+        self.for_each_ray(|ray| {
+            blocking.clear();
+            for (k, (i, j)) in ray.enumerate() {
+                let height = self.trees[i][j];
+                while blocking.last().is_some_and(|&(_, blocking_height)| blocking_height < height) {
+                    blocking.pop();
+                }
+
+                // Up to the edge when nothing blocks the view
+                let distance = k - blocking.last().map_or(0, |&(b, _)| b);
+                scores[i][j] *= distance as u64;
+
+                // A tree as tall and further away can't block anything this one doesn't
+                if blocking.last().is_some_and(|&(_, blocking_height)| blocking_height == height) {
+                    blocking.pop();
+                }
+                blocking.push((k, height));
+            }
+        });
+
+        scores
+    }
+
+    pub fn visible_trees(&self) -> usize {
+        self.visibility_map().iter().flatten().filter(|&&visible| visible).count()
+    }
+
+    // Position of the tree with the highest scenic score and the score, the first one in reading
+    // order when several trees have it
+    pub fn best_camp(&self) -> Option<((usize, usize), u64)> {
+        best_score(&self.scenic_scores())
+    }
+
+    // Calls `f` with the positions of every row and every column, once from each end
+    fn for_each_ray<F: FnMut(&mut dyn Iterator<Item = (usize, usize)>)>(&self, mut f: F) {
+        let (width, height) = (self.width(), self.height());
+
+        for i in 0..height {
+            f(&mut (0..width).map(|j| (i, j)));
+            f(&mut (0..width).rev().map(|j| (i, j)));
+        }
+        for j in 0..width {
+            f(&mut (0..height).map(|i| (i, j)));
+            f(&mut (0..height).rev().map(|i| (i, j)));
+        }
+    }
+
+    fn get_trees_from_left(&self, pos: (usize, usize)) -> Vec<&u32> {
+        self.trees[pos.0][0..pos.1].iter().collect::<Vec<&u32>>()
+    }
+
+    fn get_trees_from_right(&self, pos: (usize, usize)) -> Vec<&u32> {
+        self.trees[pos.0][pos.1 + 1..self.trees[pos.0].len()].iter().collect::<Vec<&u32>>()
+    }
+
+    fn get_trees_from_above(&self, pos: (usize, usize)) -> Vec<&u32> {
+        self.trees[0..pos.0].iter().map(|e| &e[pos.1]).collect::<Vec<&u32>>()
+    }
+
+    fn get_trees_from_below(&self, pos: (usize, usize)) -> Vec<&u32> {
+        self.trees[pos.0 + 1..self.trees.len()].iter().map(|e| &e[pos.1]).collect::<Vec<&u32>>()
+    }
+}
+
+// Forest with heights from a linear congruential generator, always the same one for the same size.
+// Used to compare the implementations on forests with more trees than the example.
+#[doc(hidden)]
+pub fn generated_forest(width: usize, height: usize) -> Forest {
+    let mut seed = 12345u64;
+    let trees = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    ((seed >> 33) % 10) as u32
+                })
+                .collect()
+        })
+        .collect();
+
+    Forest { trees }
+}
+
+fn best_score(scores: &[Vec<u64>]) -> Option<((usize, usize), u64)> {
+    scores
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, &score)| ((i, j), score)))
+        .rev()
+        .max_by_key(|&(_, score)| score)
+}
+
+impl From<&str> for Forest {
+    fn from(input: &str) -> Self {
+        let lines = lines(input);
+
+        let mut rows = Vec::with_capacity(lines.len());
+
+        for line in lines {
+            let tree_hights = line.chars().collect::<Vec<char>>();
+            let mut cols = Vec::with_capacity(tree_hights.len());
+
+            for th in tree_hights {
+                cols.push(th.to_digit(10).unwrap())
+            }
+
+            rows.push(cols)
+        }
+
+        Self {
+            trees: rows
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Answer {
+    Ok(Forest::from(input).visible_trees().to_string())
+}
+
+pub fn part_two(input: &str) -> Answer {
+    Forest::from(input)
+        .best_camp()
+        .map(|(_, score)| score.to_string())
+        .ok_or_else(|| String::from("The forest is empty"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forest_can_be_represented_as_a_matrix() {
+        let forest = Forest::from(input());
+
+        assert_eq!(forest.tree_hight((0, 0)), 3);
+        assert_eq!(forest.tree_hight((2, 2)), 3);
+        assert_eq!(forest.tree_hight((3, 3)), 4);
+    }
+
+    #[test]
+    fn is_possible_to_know_if_a_tree_is_visible() {
+        let forest = Forest::from(input());
+
+        assert!(forest.tree_is_visible((0, 0)));
+        assert!(forest.tree_is_visible((3, 0)));
+        assert!(forest.tree_is_visible((4, 4)));
+        assert!(forest.tree_is_visible((2, 4)));
+
+        assert!(forest.tree_is_visible((1, 1)));
+        assert!(forest.tree_is_visible((2, 3)));
+    }
+
+    #[test]
+    fn total_number_of_visible_trees_can_be_obtained() {
+        let forest = Forest::from(input());
+
+        assert_eq!(forest.get_number_of_visible_trees(), 21);
+    }
+
+    #[test]
+    fn visibility_from_a_tree_can_be_obtained() {
+        let forest = Forest::from(input());
+
+        assert_eq!(forest.get_visibility_from_tree((1, 2)), 4);
+        assert_eq!(forest.get_visibility_from_tree((3, 2)), 8);
+    }
+
+    #[test]
+    fn visibility_map_matches_the_visibility_of_every_tree() {
+        for forest in [Forest::from(input()), generated_forest(37, 23)] {
+            let visible = forest.visibility_map();
+
+            for (i, row) in visible.iter().enumerate() {
+                for (j, &visible) in row.iter().enumerate() {
+                    assert_eq!(visible, forest.tree_is_visible((i, j)), "Tree at {:?}", (i, j));
+                }
+            }
+            assert_eq!(forest.visible_trees() as u32, forest.get_number_of_visible_trees());
+        }
+    }
+
+    #[test]
+    fn scenic_scores_match_the_visibility_from_every_tree() {
+        for forest in [Forest::from(input()), generated_forest(37, 23)] {
+            let scores = forest.scenic_scores();
+
+            for (i, row) in scores.iter().enumerate() {
+                for (j, &score) in row.iter().enumerate() {
+                    assert_eq!(score, forest.get_visibility_from_tree((i, j)) as u64, "Tree at {:?}", (i, j));
+                }
+            }
+            assert_eq!(forest.best_camp().map(|(_, score)| score), Some(forest.get_best_position_to_place_the_camp() as u64));
+        }
+    }
+
+    #[test]
+    fn scenic_scores_of_large_forests_do_not_overflow() {
+        // The tall tree in the middle sees 500 trees in every direction
+        let size = 1001;
+        let mut trees = vec![vec![0; size]; size];
+        trees[size / 2][size / 2] = 9;
+
+        assert_eq!(Forest { trees }.best_camp(), Some(((500, 500), 500u64.pow(4))));
+    }
+
+    #[test]
+    fn best_camp_is_found() {
+        assert_eq!(Forest::from(input()).best_camp(), Some(((3, 2), 8)));
+        assert_eq!(Forest::from("").best_camp(), None);
+    }
+
+    #[test]
+    fn both_parts_are_solved_for_the_example() {
+        assert_eq!(part_one(input()), Ok(String::from("21")));
+        assert_eq!(part_two(input()), Ok(String::from("8")));
+    }
+
+    fn input() -> &'static str {
+        r"30373
+25512
+65332
+33549
+35390"
+    }
+}
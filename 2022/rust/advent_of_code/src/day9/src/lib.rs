@@ -0,0 +1,388 @@
+#![deny(unused)]
+
+pub mod render;
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+use aoc_common::{lines, read_input, Answer, Day, Part};
+
+pub const DAY: Day = Day {
+    number: 9,
+    title: "Rope Bridge",
+    input: || read_input!(),
+    parts: [
+        Part { description: "Positions visited by the tail of a rope of 2 knots", solve: part_one },
+        Part { description: "Positions visited by the tail of a rope of 10 knots", solve: part_two },
+    ],
+};
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Move {
+    Up(u32),
+    Right(u32),
+    Down(u32),
+    Left(u32),
+    Unknown(String),
+}
+
+impl From<&str> for Move {
+    fn from(input: &str) -> Self {
+        let mut split = input.split(' ');
+        let direction = split.next().unwrap();
+        let amount = split.next().unwrap().parse::<u32>().unwrap();
+
+        match direction {
+            "U" => Move::Up(amount),
+            "R" => Move::Right(amount),
+            "D" => Move::Down(amount),
+            "L" => Move::Left(amount),
+            _ => Move::Unknown(direction.to_string())
+        }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Move::Up(amount) => write!(f, "U {}", amount),
+            Move::Right(amount) => write!(f, "R {}", amount),
+            Move::Down(amount) => write!(f, "D {}", amount),
+            Move::Left(amount) => write!(f, "L {}", amount),
+            Move::Unknown(direction) => write!(f, "{}", direction),
+        }
+    }
+}
+
+impl Move {
+    // Number of positions to move and a move of a single position in the same direction
+    pub fn steps(&self) -> Option<(u32, Move)> {
+        match *self {
+            Move::Up(y) => Some((y, Move::Up(1))),
+            Move::Right(x) => Some((x, Move::Right(1))),
+            Move::Down(y) => Some((y, Move::Down(1))),
+            Move::Left(x) => Some((x, Move::Left(1))),
+            Move::Unknown(_) => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub struct Position {
+    x: i32,
+    y: i32,
+}
+
+impl Position {
+    pub fn new() -> Self {
+        Self { x: 0, y: 0 }
+    }
+
+    pub fn update(&mut self, mv: &Move) {
+        match *mv {
+            Move::Up(y) => self.y += y as i32,
+            Move::Right(x) => self.x += x as i32,
+            Move::Down(y) => self.y -= y as i32,
+            Move::Left(x) => self.x -= x as i32,
+            Move::Unknown(_) => {}
+        }
+    }
+
+    // Number of king moves between the positions, so knots touching diagonally are at 1
+    pub fn distance_to(&self, other: &Position) -> u32 {
+        (other.x - self.x).unsigned_abs().max((other.y - self.y).unsigned_abs())
+    }
+
+    // One step in each axis towards the other position, or none when it's already aligned in it
+    pub fn step_towards(&mut self, other: &Position) {
+        self.x += (other.x - self.x).signum();
+        self.y += (other.y - self.y).signum();
+    }
+}
+
+#[derive(Clone)]
+pub struct Knot {
+    pos: Position,
+    visited_positions: HashSet<Position>,
+}
+
+impl Default for Knot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Knot {
+    pub fn new() -> Self {
+        Self {
+            pos: Position::new(),
+            visited_positions: HashSet::from([Position::new()]),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Rope {
+    knots: Vec<Knot>,
+}
+
+impl Rope {
+    // A rope always has at least its head
+    pub fn new(knots: usize) -> Self {
+        Self {
+            knots: (0..knots.max(1)).map(|_| Knot::new()).collect(),
+        }
+    }
+
+    pub fn move_rope(&mut self, mv: Move) {
+        let Some((steps, step)) = mv.steps() else {
+            return;
+        };
+
+        for _ in 0..steps {
+            self.move_head(&step);
+        }
+    }
+
+    // Moves the head one position and every other knot after the one in front of it
+    pub fn move_head(&mut self, step: &Move) {
+        let head = &mut self.knots[0];
+        head.pos.update(step);
+        head.visited_positions.insert(head.pos);
+
+        for i in 1..self.knots.len() {
+            if !self.follow(i) {
+                // The knots behind one that didn't move don't move either
+                break;
+            }
+        }
+    }
+
+    // A knot only moves when it's no longer touching the one in front of it, and then it steps
+    // towards it, diagonally if they aren't in the same row or column
+    fn follow(&mut self, pos: usize) -> bool {
+        let leader = self.knots[pos - 1].pos;
+        let knot = &mut self.knots[pos];
+
+        if knot.pos.distance_to(&leader) <= 1 {
+            return false;
+        }
+
+        knot.pos.step_towards(&leader);
+        knot.visited_positions.insert(knot.pos);
+
+        true
+    }
+
+    #[cfg(test)]
+    pub fn get_head(&self) -> &Knot {
+        self.knots.first().unwrap()
+    }
+
+    pub fn get_tail(&self) -> &Knot {
+        self.knots.last().unwrap()
+    }
+}
+
+pub fn part_one(input: &str) -> Answer {
+    Ok(tail_visited_positions(input, 2).to_string())
+}
+
+pub fn part_two(input: &str) -> Answer {
+    Ok(tail_visited_positions(input, 10).to_string())
+}
+
+pub fn tail_visited_positions(input: &str, knots: usize) -> usize {
+    let mut rope = Rope::new(knots);
+    for mv in parse_moves(input) {
+        rope.move_rope(mv);
+    }
+
+    rope.get_tail().visited_positions.len()
+}
+
+pub fn parse_moves(input: &str) -> Vec<Move> {
+    lines(input).into_iter().map(Move::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn movement_is_correctly_identified() {
+        let mv = Move::from("U 23");
+        assert_eq!(mv, Move::Up(23));
+
+        let mv = Move::from("D 16");
+        assert_eq!(mv, Move::Down(16));
+
+        let mv = Move::from("R 209");
+        assert_eq!(mv, Move::Right(209));
+
+        let mv = Move::from("L 1");
+        assert_eq!(mv, Move::Left(1));
+    }
+
+    #[test]
+    fn position_can_be_updated_using_a_move() {
+        let mut pos = Position::new();
+
+        pos.update(&Move::Up(2));
+        assert_eq!(pos, Position { x: 0, y: 2 });
+
+        pos.update(&Move::Right(4));
+        assert_eq!(pos, Position { x: 4, y: 2 });
+
+        pos.update(&Move::Down(8));
+        assert_eq!(pos, Position { x: 4, y: -6 });
+
+        pos.update(&Move::Left(2));
+        assert_eq!(pos, Position { x: 2, y: -6 });
+    }
+
+    #[test]
+    fn position_steps_towards_other_one_axis_at_a_time() {
+        let mut pos = Position { x: 45, y: -34 };
+
+        pos.step_towards(&Position { x: 45, y: -30 });
+        assert_eq!(pos, Position { x: 45, y: -33 });
+
+        pos.step_towards(&Position { x: 47, y: -31 });
+        assert_eq!(pos, Position { x: 46, y: -32 });
+
+        pos.step_towards(&Position { x: 40, y: -32 });
+        assert_eq!(pos, Position { x: 45, y: -32 });
+
+        pos.step_towards(&Position { x: 45, y: -32 });
+        assert_eq!(pos, Position { x: 45, y: -32 });
+    }
+
+    #[test]
+    fn distance_between_positions_can_be_known() {
+        let pos = Position::new();
+        assert_eq!(pos.distance_to(&Position { x: 1, y: 0 }), 1);
+        assert_eq!(pos.distance_to(&Position { x: 0, y: -1 }), 1);
+        assert_eq!(pos.distance_to(&Position { x: 1, y: 1 }), 1);
+        assert_eq!(pos.distance_to(&Position { x: 2, y: 2 }), 2);
+        assert_eq!(pos.distance_to(&Position { x: -3, y: 1 }), 3);
+        assert_eq!(pos.distance_to(&Position { x: 1, y: -5 }), 5);
+    }
+
+    #[test]
+    fn tail_is_moved_when_head_is_moved_and_the_distance_remains_one() {
+        let mut rope = Rope::new(2);
+        // H (H over T over s)
+
+        rope.move_rope(Move::Right(4));
+        // s . . T H
+
+        assert_eq!(rope.get_head().pos, Position { x: 4, y: 0 });
+        assert_eq!(rope.get_tail().pos, Position { x: 3, y: 0 });
+
+        rope.move_rope(Move::Up(1));
+        // . . . . H
+        // s . . T .
+
+        assert_eq!(rope.get_head().pos, Position { x: 4, y: 1 });
+        assert_eq!(rope.get_tail().pos, Position { x: 3, y: 0 });
+
+        rope.move_rope(Move::Right(1));
+        // . . . . T H
+        // s . . . . .
+
+        assert_eq!(rope.get_head().pos, Position { x: 5, y: 1 });
+        assert_eq!(rope.get_tail().pos, Position { x: 4, y: 1 });
+
+        rope.move_rope(Move::Left(1));
+        // . . . . H . (H over T)
+        // s . . . . .
+
+        assert_eq!(rope.get_head().pos, Position { x: 4, y: 1 });
+        assert_eq!(rope.get_tail().pos, Position { x: 4, y: 1 });
+
+        rope.move_rope(Move::Left(4));
+        // . . . . H . (H over T)
+        // s . . . . .
+
+        assert_eq!(rope.get_head().pos, Position { x: 0, y: 1 });
+        assert_eq!(rope.get_tail().pos, Position { x: 1, y: 1 });
+    }
+
+    #[test]
+    fn tail_visited_positions_can_be_obtained() {
+        let mut rope = Rope::new(2);
+        rope.move_rope(Move::from("R 4"));
+        rope.move_rope(Move::from("U 4"));
+        rope.move_rope(Move::from("L 3"));
+        rope.move_rope(Move::from("D 1"));
+        rope.move_rope(Move::from("R 4"));
+        rope.move_rope(Move::from("D 1"));
+        rope.move_rope(Move::from("L 5"));
+        rope.move_rope(Move::from("R 2"));
+        /*
+        . . . . . .
+        . . . . . .
+        . T H . . .
+        . . . . . .
+        s . . . . .
+
+        . . # # . .
+        . . . # # .
+        . # # # # .
+        . . . . # .
+        s # # # . .
+        */
+
+        assert_eq!(rope.get_tail().visited_positions.len(), 13);
+    }
+
+    const MOVES: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
+
+    const LARGER_MOVES: &str = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20";
+
+    #[test]
+    fn tail_of_a_long_rope_follows_diagonal_moves() {
+        let mut rope = Rope::new(10);
+        rope.move_rope(Move::from("R 5"));
+        rope.move_rope(Move::from("U 8"));
+
+        let knots = rope.knots.iter().map(|knot| (knot.pos.x, knot.pos.y)).collect::<Vec<(i32, i32)>>();
+        assert_eq!(knots, vec![(5, 8), (5, 7), (5, 6), (5, 5), (5, 4), (4, 4), (3, 3), (2, 2), (1, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn tail_visited_positions_are_obtained_for_the_examples() {
+        assert_eq!(tail_visited_positions(MOVES, 2), 13);
+        assert_eq!(tail_visited_positions(MOVES, 10), 1);
+        assert_eq!(tail_visited_positions(LARGER_MOVES, 10), 36);
+    }
+
+    #[test]
+    fn ropes_of_any_length_can_be_moved() {
+        // A rope of a single knot is just the head
+        assert_eq!(tail_visited_positions("R 4\nU 4", 1), 9);
+        assert_eq!(tail_visited_positions("R 4\nU 4", 0), 9);
+
+        // Along a straight line every knot but the head ends one position behind the previous one
+        for knots in 1..=25 {
+            assert_eq!(tail_visited_positions("R 20", knots), 21usize.saturating_sub(knots - 1).max(1));
+        }
+
+        for knots in 1..=30 {
+            let mut rope = Rope::new(knots);
+            for line in lines(LARGER_MOVES) {
+                rope.move_rope(Move::from(line));
+                assert!(rope.knots.windows(2).all(|pair| pair[0].pos.distance_to(&pair[1].pos) <= 1));
+            }
+            assert_eq!(rope.knots.len(), knots);
+        }
+    }
+
+    #[test]
+    fn both_parts_are_solved_for_the_examples() {
+        assert_eq!(part_one(MOVES), Ok(String::from("13")));
+        assert_eq!(part_two(LARGER_MOVES), Ok(String::from("36")));
+    }
+}
@@ -0,0 +1,235 @@
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::{Move, Position, Rope};
+
+// Smallest rectangle with the start and a set of positions, the rows go from the top (highest y)
+// to the bottom like in the puzzle drawings
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Bounds {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Bounds {
+    pub fn around<I: IntoIterator<Item = Position>>(positions: I) -> Self {
+        positions.into_iter().fold(Bounds { min: Position::new(), max: Position::new() }, |bounds, pos| Bounds {
+            min: Position { x: bounds.min.x.min(pos.x), y: bounds.min.y.min(pos.y) },
+            max: Position { x: bounds.max.x.max(pos.x), y: bounds.max.y.max(pos.y) },
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    // Row and column of the position in a drawing, none if it's outside the bounds
+    fn cell(&self, pos: Position) -> Option<(usize, usize)> {
+        let inside = (self.min.x..=self.max.x).contains(&pos.x) && (self.min.y..=self.max.y).contains(&pos.y);
+        inside.then(|| ((self.max.y - pos.y) as usize, (pos.x - self.min.x) as usize))
+    }
+
+    fn draw(&self, cells: &[(Position, char)]) -> String {
+        let mut grid = vec![vec!['.'; self.width()]; self.height()];
+        for &(pos, c) in cells {
+            if let Some((row, column)) = self.cell(pos) {
+                grid[row][column] = c;
+            }
+        }
+
+        grid.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<String>>().join("\n")
+    }
+}
+
+impl Rope {
+    // Everywhere any knot has been
+    pub fn bounds(&self) -> Bounds {
+        Bounds::around(self.knots.iter().flat_map(|knot| knot.visited_positions.iter().copied()))
+    }
+
+    pub fn render(&self) -> String {
+        self.render_within(&self.bounds())
+    }
+
+    // Same symbols as the puzzle, the positions visited by the tail marked with `#`. Knots cover the
+    // ones behind them, and all of them cover the start and the visited positions.
+    pub fn render_within(&self, bounds: &Bounds) -> String {
+        let mut cells = self.get_tail().visited_positions.iter().map(|&pos| (pos, '#')).collect::<Vec<(Position, char)>>();
+        cells.push((Position::new(), 's'));
+        cells.extend(self.knots.iter().enumerate().rev().map(|(i, knot)| (knot.pos, self.label(i))));
+
+        bounds.draw(&cells)
+    }
+
+    // `H` for the head and the number of the knot for the rest, or `T` for the tail of a rope of
+    // two knots
+    fn label(&self, knot: usize) -> char {
+        match knot {
+            0 => 'H',
+            1 if self.knots.len() == 2 => 'T',
+            _ => char::from_digit(knot as u32, 36).unwrap_or('*'),
+        }
+    }
+
+    // Positions visited by the tail with `#` and the start with `s`, like in the puzzle
+    pub fn render_visited(&self) -> String {
+        let visited = &self.get_tail().visited_positions;
+
+        let mut cells = visited.iter().map(|&pos| (pos, '#')).collect::<Vec<(Position, char)>>();
+        cells.push((Position::new(), 's'));
+
+        Bounds::around(visited.iter().copied()).draw(&cells)
+    }
+
+    // Binary PPM of the positions visited by the tail in white, with the start in red
+    pub fn visited_ppm(&self) -> Vec<u8> {
+        let visited = &self.get_tail().visited_positions;
+        let bounds = Bounds::around(visited.iter().copied());
+
+        let mut pixels = vec![[0u8; 3]; bounds.width() * bounds.height()];
+        for &pos in visited {
+            if let Some((row, column)) = bounds.cell(pos) {
+                pixels[row * bounds.width() + column] = [255, 255, 255];
+            }
+        }
+        if let Some((row, column)) = bounds.cell(Position::new()) {
+            pixels[row * bounds.width() + column] = [255, 0, 0];
+        }
+
+        let mut image = format!("P6\n{} {}\n255\n", bounds.width(), bounds.height()).into_bytes();
+        image.extend(pixels.iter().flatten());
+
+        image
+    }
+
+    // Applies the moves one position at a time, redrawing the rope after every one of them. All the
+    // frames have the size needed by the whole replay, so the drawing doesn't jump around.
+    pub fn replay<W: Write>(&mut self, moves: &[Move], out: &mut W, delay: Duration) -> io::Result<()> {
+        let mut end = self.clone();
+        for mv in moves {
+            end.move_rope(mv.clone());
+        }
+        let bounds = end.bounds();
+
+        self.draw_frame(out, "Start", &bounds)?;
+        for (number, mv) in (1..).zip(moves) {
+            let Some((steps, step)) = mv.steps() else {
+                continue;
+            };
+
+            for position in 1..=steps {
+                thread::sleep(delay);
+                self.move_head(&step);
+
+                let title = format!("Move {}/{}: {} ({}/{})", number, moves.len(), mv, position, steps);
+                self.draw_frame(out, &title, &bounds)?;
+            }
+        }
+
+        writeln!(out, "\nPositions visited by the tail: {}", self.get_tail().visited_positions.len())
+    }
+
+    fn draw_frame<W: Write>(&self, out: &mut W, title: &str, bounds: &Bounds) -> io::Result<()> {
+        // Clear the screen and move the cursor to the top left corner
+        write!(out, "\x1b[2J\x1b[H")?;
+        writeln!(out, "{}\n\n{}", title, self.render_within(bounds))?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_moves;
+
+    const MOVES: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
+
+    fn rope_after(knots: usize, moves: &str) -> Rope {
+        let mut rope = Rope::new(knots);
+        for mv in parse_moves(moves) {
+            rope.move_rope(mv);
+        }
+
+        rope
+    }
+
+    #[test]
+    fn rope_is_drawn_with_the_head_and_the_tail() {
+        assert_eq!(Rope::new(2).render(), "H");
+        assert_eq!(rope_after(2, "R 4").render(), "s##TH");
+        assert_eq!(rope_after(2, "R 4\nU 2").render(), "....H\n....T\ns###.");
+    }
+
+    #[test]
+    fn knots_are_numbered_and_cover_the_ones_behind() {
+        let expected = "\
+.....H
+.....1
+.....2
+.....3
+....54
+...6..
+..7...
+.8....
+9.....";
+        assert_eq!(rope_after(10, "R 5\nU 8").render(), expected);
+
+        assert_eq!(rope_after(40, "R 1").render(), "1H");
+        assert_eq!(rope_after(40, "R 40").render().chars().filter(|&c| c != '.').collect::<String>(), "s****zyxwvutsrqponmlkjihgfedcba987654321H");
+    }
+
+    #[test]
+    fn rope_can_be_drawn_within_other_bounds() {
+        let rope = rope_after(2, "R 4");
+        let bounds = Bounds { min: Position { x: 2, y: -1 }, max: Position { x: 6, y: 0 } };
+
+        assert_eq!(rope.render_within(&bounds), "#TH..\n.....");
+    }
+
+    #[test]
+    fn visited_positions_are_drawn_like_in_the_puzzle() {
+        let expected = "\
+..##.
+...##
+.####
+....#
+s###.";
+        assert_eq!(rope_after(2, MOVES).render_visited(), expected);
+        assert_eq!(rope_after(10, MOVES).render_visited(), "s");
+    }
+
+    #[test]
+    fn visited_positions_are_exported_as_ppm() {
+        let image = rope_after(2, MOVES).visited_ppm();
+
+        let header = b"P6\n5 5\n255\n";
+        assert_eq!(&image[..header.len()], header);
+
+        let pixels = image[header.len()..].chunks(3).collect::<Vec<&[u8]>>();
+        assert_eq!(pixels.len(), 25);
+        assert_eq!(pixels.iter().filter(|&&pixel| pixel == [255, 255, 255]).count(), 12);
+        assert_eq!(pixels[4 * 5], [255, 0, 0]);
+        assert_eq!(pixels[0], [0, 0, 0]);
+    }
+
+    #[test]
+    fn replay_draws_every_step() {
+        let mut rope = Rope::new(2);
+        let mut out = Vec::new();
+        rope.replay(&parse_moves(MOVES), &mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        // The start and one frame per position moved by the head
+        assert_eq!(out.matches("\x1b[2J").count(), 25);
+        assert!(out.contains("Start\n\n......\n......\n......\n......\nH.....\n"));
+        assert!(out.contains("Move 1/8: R 4 (1/4)\n\n......\n......\n......\n......\nTH....\n"));
+        assert!(out.contains("Move 8/8: R 2 (2/2)\n\n..##..\n...##.\n.TH##.\n....#.\ns###..\n"));
+        assert!(out.ends_with("Positions visited by the tail: 13\n"));
+        assert_eq!(rope.render_visited(), rope_after(2, MOVES).render_visited());
+    }
+}
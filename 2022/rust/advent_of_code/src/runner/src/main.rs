@@ -0,0 +1,152 @@
+use std::env;
+use std::ops::RangeInclusive;
+use std::panic;
+use std::process;
+
+use aoc_common::{format_answer, part_name, Day};
+
+// Solved days only, the ones still in progress would make every run fail
+const DAYS: [Day; 11] = [
+    day1::DAY,
+    day2::DAY,
+    day3::DAY,
+    day4::DAY,
+    day5::DAY,
+    day6::DAY,
+    day7::DAY,
+    day8::DAY,
+    day9::DAY,
+    day10::DAY,
+    day11::DAY,
+];
+
+const USAGE: &str = "Usage: runner [all | <day> | <first day>-<last day>]";
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    let selection = match parse_selection(&args) {
+        Ok(selection) => selection,
+        Err(error) => {
+            eprintln!("{}", error);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let mut failed = false;
+    for day in DAYS.iter().filter(|day| selection.contains(&day.number)) {
+        failed |= !run(day);
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+// Solves both parts of a day, returns if all of them were solved. A panicking day is reported as a
+// failure instead of stopping the days after it.
+fn run(day: &Day) -> bool {
+    println!("Day {}: {}", day.number, day.title);
+
+    let input = match panic::catch_unwind(day.input) {
+        Ok(input) => input,
+        Err(_) => {
+            eprintln!("Day {} failed: Cannot read the input", day.number);
+            return false;
+        }
+    };
+
+    let mut solved = true;
+    for (part, solution) in (1..).zip(day.parts.iter()) {
+        match panic::catch_unwind(|| (solution.solve)(&input)) {
+            Ok(Ok(answer)) => println!("{}", format_answer(part, solution.description, answer)),
+            Ok(Err(error)) => {
+                eprintln!("Day {} part {} failed: {}", day.number, part_name(part), error);
+                solved = false;
+            }
+            Err(_) => {
+                eprintln!("Day {} part {} failed: Panicked", day.number, part_name(part));
+                solved = false;
+            }
+        }
+    }
+
+    solved
+}
+
+// Days to run: all of them, a single one or an inclusive range like `3-7`
+fn parse_selection(args: &[String]) -> Result<RangeInclusive<u32>, String> {
+    let first = DAYS[0].number;
+    let last = DAYS[DAYS.len() - 1].number;
+
+    let selection = match args {
+        [] => return Ok(first..=last),
+        [selection] if selection == "all" => return Ok(first..=last),
+        [selection] => selection,
+        _ => return Err(String::from("Too many arguments")),
+    };
+
+    let (from, to) = match selection.split_once('-') {
+        Some((from, to)) => (parse_day(from)?, parse_day(to)?),
+        None => {
+            let day = parse_day(selection)?;
+            (day, day)
+        }
+    };
+
+    if from > to {
+        return Err(format!("Invalid range of days: {}", selection));
+    }
+    if from < first || to > last {
+        return Err(format!("Only days {} to {} are available", first, last));
+    }
+
+    Ok(from..=to)
+}
+
+fn parse_day(day: &str) -> Result<u32, String> {
+    day.trim().parse::<u32>().map_err(|_| format!("Invalid day: {}", day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn days_are_in_order() {
+        for (number, day) in (1..).zip(DAYS.iter()) {
+            assert_eq!(day.number, number);
+        }
+    }
+
+    #[test]
+    fn all_days_are_selected_by_default() {
+        assert_eq!(parse_selection(&args(&[])), Ok(1..=11));
+        assert_eq!(parse_selection(&args(&["all"])), Ok(1..=11));
+    }
+
+    #[test]
+    fn a_single_day_can_be_selected() {
+        assert_eq!(parse_selection(&args(&["5"])), Ok(5..=5));
+    }
+
+    #[test]
+    fn a_range_of_days_can_be_selected() {
+        assert_eq!(parse_selection(&args(&["3-7"])), Ok(3..=7));
+    }
+
+    #[test]
+    fn invalid_selections_are_rejected() {
+        assert!(parse_selection(&args(&["0"])).is_err());
+        assert!(parse_selection(&args(&["12"])).is_err());
+        assert!(parse_selection(&args(&["7-3"])).is_err());
+        assert!(parse_selection(&args(&["one"])).is_err());
+        assert!(parse_selection(&args(&["1-"])).is_err());
+        assert!(parse_selection(&args(&["1", "2"])).is_err());
+    }
+}
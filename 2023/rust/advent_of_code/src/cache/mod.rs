@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const CACHE_FILE: &str = "cache/answers.txt";
+
+// Version of the crate that computed the answer. It only changes when the crate version is bumped,
+// so after a code change the cached answers are still served until the version is bumped or the
+// runner is run with `--force`
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CacheKey {
+    pub day: u32,
+    pub part: u32,
+    pub input_hash: u64,
+    pub version: String,
+}
+
+impl CacheKey {
+    pub fn new(day: u32, part: u32, input: &[String]) -> CacheKey {
+        CacheKey {
+            day,
+            part,
+            input_hash: hash_input(input),
+            version: VERSION.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub answer: String,
+    pub duration: Duration,
+}
+
+// Answers already computed, stored as one tab separated line per entry:
+// `<day> <part> <input hash> <version> <answer> <duration in nanoseconds>`
+pub struct Cache {
+    path: PathBuf,
+    entries: BTreeMap<CacheKey, CacheEntry>,
+}
+
+impl Cache {
+    // Loads the cache from the given file. A missing file is an empty cache and malformed lines are
+    // ignored, they will be overwritten the next time the cache is saved.
+    pub fn load(path: &Path) -> Cache {
+        let mut cache = Cache {
+            path: path.to_path_buf(),
+            entries: BTreeMap::new(),
+        };
+
+        if let Ok(content) = fs::read_to_string(path) {
+            for line in content.lines() {
+                if let Some((key, entry)) = parse_line(line) {
+                    cache.entries.insert(key, entry);
+                }
+            }
+        }
+
+        cache
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = self
+            .entries
+            .iter()
+            .map(|(key, entry)| format_line(key, entry))
+            .collect::<String>();
+
+        fs::write(&self.path, content)
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<&CacheEntry> {
+        self.entries.get(key)
+    }
+
+    pub fn insert(&mut self, key: CacheKey, entry: CacheEntry) {
+        self.entries.insert(key, entry);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&CacheKey, &CacheEntry)> {
+        self.entries.iter()
+    }
+
+    // Keeps only the entries for which the predicate is true and returns how many were removed
+    pub fn retain<F>(&mut self, mut keep: F) -> usize
+    where
+        F: FnMut(&CacheKey) -> bool,
+    {
+        let before = self.entries.len();
+        self.entries.retain(|key, _| keep(key));
+        before - self.entries.len()
+    }
+}
+
+// 64 bit FNV-1a hash of the input lines. Unlike the std hashers, its value is stable across
+// compilers and platforms, so it can be persisted.
+pub fn hash_input(input: &[String]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut hash = OFFSET_BASIS;
+
+    for line in input {
+        for byte in line.bytes().chain([b'\n']) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+
+    hash
+}
+
+fn format_line(key: &CacheKey, entry: &CacheEntry) -> String {
+    format!(
+        "{}\t{}\t{:016x}\t{}\t{}\t{}\n",
+        key.day,
+        key.part,
+        key.input_hash,
+        key.version,
+        escape(&entry.answer),
+        entry.duration.as_nanos()
+    )
+}
+
+fn parse_line(line: &str) -> Option<(CacheKey, CacheEntry)> {
+    let fields = line.split('\t').collect::<Vec<&str>>();
+    if fields.len() != 6 {
+        return None;
+    }
+
+    let key = CacheKey {
+        day: fields[0].parse().ok()?,
+        part: fields[1].parse().ok()?,
+        input_hash: u64::from_str_radix(fields[2], 16).ok()?,
+        version: fields[3].to_string(),
+    };
+
+    let entry = CacheEntry {
+        answer: unescape(fields[4]),
+        duration: Duration::from_nanos(fields[5].parse().ok()?),
+    };
+
+    Some((key, entry))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn entry(answer: &str) -> CacheEntry {
+        CacheEntry {
+            answer: answer.to_string(),
+            duration: Duration::from_micros(1234),
+        }
+    }
+
+    #[test]
+    fn the_input_hash_is_stable() {
+        assert_eq!(hash_input(&[]), 0xcbf29ce484222325);
+        assert_eq!(hash_input(&[String::from("a")]), 0x089bdc07b544e7b2);
+    }
+
+    #[test]
+    fn different_inputs_have_different_hashes() {
+        let input_a = vec![String::from("ab"), String::from("c")];
+        let input_b = vec![String::from("a"), String::from("bc")];
+
+        assert_ne!(hash_input(&input_a), hash_input(&input_b));
+    }
+
+    #[test]
+    fn the_cache_can_be_saved_and_loaded_again() {
+        // Unique per run, so that test runs in parallel don't share the file
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let path = env::temp_dir().join(format!("advent_of_code_cache_{}_{}.txt", process::id(), nanos));
+
+        let mut cache = Cache::load(&path);
+        let key = CacheKey::new(1, 2, &[String::from("input")]);
+        cache.insert(key.clone(), entry("a\tmulti\nline \\ answer"));
+        cache.save().unwrap();
+
+        let cache = Cache::load(&path);
+        assert_eq!(cache.get(&key), Some(&entry("a\tmulti\nline \\ answer")));
+        assert_eq!(cache.entries().count(), 1);
+
+        fs::remove_file(path).expect("Cannot remove cache file");
+    }
+
+    #[test]
+    fn a_missing_cache_file_is_an_empty_cache() {
+        let cache = Cache::load(Path::new("cache/does-not-exist.txt"));
+
+        assert_eq!(cache.entries().count(), 0);
+    }
+
+    #[test]
+    fn malformed_lines_in_the_cache_file_are_ignored() {
+        let key = CacheKey::new(3, 1, &[]);
+        let line = format_line(&key, &entry("42"));
+
+        assert_eq!(parse_line(&line[..line.len() - 1]), Some((key, entry("42"))));
+        assert_eq!(parse_line("3\t1\tnot-a-hash\t0.1.0\t42\t1"), None);
+        assert_eq!(parse_line("garbage"), None);
+    }
+
+    #[test]
+    fn entries_can_be_dropped_from_the_cache() {
+        let mut cache = Cache::load(Path::new("cache/does-not-exist.txt"));
+        cache.insert(CacheKey::new(1, 1, &[]), entry("1"));
+        cache.insert(CacheKey::new(2, 1, &[]), entry("2"));
+
+        let removed = cache.retain(|key| key.day == 2);
+
+        assert_eq!(removed, 1);
+        assert_eq!(cache.entries().map(|(key, _)| key.day).collect::<Vec<u32>>(), vec![2]);
+    }
+}
@@ -3,11 +3,11 @@ use advent_of_code::day01;
 fn main() {
     let input = advent_of_code::read_input_file("input01.txt");
 
-    let result = day01::run(&input, false);
+    let result = day01::run(&input, false).expect("Cannot solve part one");
     println!("Result part one: {}", result);
     advent_of_code::write_output_file("output01a.txt", &result);
 
-    let result = day01::run(&input, true);
+    let result = day01::run(&input, true).expect("Cannot solve part two");
     println!("Result part two: {}", result);
     advent_of_code::write_output_file("output01b.txt", &result);
 }
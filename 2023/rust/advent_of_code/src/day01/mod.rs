@@ -1,28 +1,31 @@
 const SPELLED_DIGITS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
 
-pub fn run(input: &Vec<String>, check_spelled: bool) -> String {
+// Lines are scanned by byte offset. Only the offsets where a char starts are visited, so slicing the
+// line at any of them is always valid, whatever the number of bytes of the chars around it.
+
+pub fn run(input: &[String], check_spelled: bool) -> Result<String, &'static str> {
     let mut result = 0;
 
     for line in input {
-        let calibration_value = get_calibration_value(&line, check_spelled);
+        let calibration_value = get_calibration_value(line, check_spelled)?;
         result += calibration_value;
     }
 
-    return result.to_string();
+    Ok(result.to_string())
 }
 
-fn get_first_digit(input: &String, check_spelled: bool) -> Result<String, &str> {
-    for i in 0..input.len() {
-        if input.chars().nth(i).unwrap().is_digit(10) {
-            return Ok(input.chars().nth(i).unwrap().to_string());
+fn get_first_digit(input: &str, check_spelled: bool) -> Result<String, &'static str> {
+    for (i, c) in input.char_indices() {
+        if c.is_ascii_digit() {
+            return Ok(c.to_string());
         }
 
         if !check_spelled {
             continue;
         }
 
-        for j in 0..SPELLED_DIGITS.len() {
-            if input[i..].starts_with(SPELLED_DIGITS[j]) {
+        for (j, spelled_digit) in SPELLED_DIGITS.iter().enumerate() {
+            if input[i..].starts_with(spelled_digit) {
                 // Needs to add 1 to the index because the digits does not include "zero"
                 return Ok((j + 1).to_string());
             }
@@ -32,18 +35,18 @@ fn get_first_digit(input: &String, check_spelled: bool) -> Result<String, &str>
     Err("No digit found")
 }
 
-fn get_last_digit(input: &String, check_spelled: bool) -> Result<String, &str> {
-    for i in (0..input.len()).rev(){
-        if input.chars().nth(i).unwrap().is_digit(10) {
-            return Ok(input.chars().nth(i).unwrap().to_string());
+fn get_last_digit(input: &str, check_spelled: bool) -> Result<String, &'static str> {
+    for (i, c) in input.char_indices().rev() {
+        if c.is_ascii_digit() {
+            return Ok(c.to_string());
         }
 
         if !check_spelled {
             continue;
         }
 
-        for j in 0..SPELLED_DIGITS.len() {
-            if input[..=i].ends_with(SPELLED_DIGITS[j]) {
+        for (j, spelled_digit) in SPELLED_DIGITS.iter().enumerate() {
+            if input[..i + c.len_utf8()].ends_with(spelled_digit) {
                 // Needs to add 1 to the index because the digits does not include "zero"
                 return Ok((j + 1).to_string());
             }
@@ -53,11 +56,11 @@ fn get_last_digit(input: &String, check_spelled: bool) -> Result<String, &str> {
     Err("No digit found")
 }
 
-fn get_calibration_value(input: &String, check_spelled: bool) -> u32 {
-    let first_digit = get_first_digit(input, check_spelled).unwrap();
-    let last_digit = get_last_digit(input, check_spelled).unwrap();
+fn get_calibration_value(input: &str, check_spelled: bool) -> Result<u32, &'static str> {
+    let first_digit = get_first_digit(input, check_spelled)?;
+    let last_digit = get_last_digit(input, check_spelled)?;
     let calibration_value = format!("{}{}", first_digit, last_digit);
-    calibration_value.parse::<u32>().unwrap()
+    calibration_value.parse::<u32>().map_err(|_| "Invalid calibration value")
 }
 
 #[cfg(test)]
@@ -76,7 +79,7 @@ mod tests {
     fn is_possible_to_get_the_last_digit_of_a_string() {
         let input = String::from("pqr3stu8vwx");
         let expected = String::from("8");
-        assert_eq!(get_last_digit(&input,false).unwrap(), expected);
+        assert_eq!(get_last_digit(&input, false).unwrap(), expected);
     }
 
     #[test]
@@ -97,27 +100,54 @@ mod tests {
     fn is_possible_to_get_the_number_formed_by_the_first_and_last_digits_of_a_string() {
         let input = String::from("pqr3stu8vwx");
         let expected = 38;
-        assert_eq!(get_calibration_value(&input, true), expected);
+        assert_eq!(get_calibration_value(&input, true).unwrap(), expected);
     }
 
     #[test]
     fn is_possible_to_get_the_number_formed_by_the_first_and_last_spelled_digits_of_a_string(){
         let input = String::from("zoneight234");
         let expected = 14;
-        assert_eq!(get_calibration_value(&input, true), expected);
+        assert_eq!(get_calibration_value(&input, true).unwrap(), expected);
     }
 
     #[test]
     fn is_possible_to_get_the_sum_of_all_calibration_values() {
         let input_file = read_input_file("example01a.txt");
         let expected = String::from("142");
-        assert_eq!(run(&input_file, false), expected.to_string());
+        assert_eq!(run(&input_file, false).unwrap(), expected.to_string());
     }
 
     #[test]
     fn is_possible_to_get_the_sum_of_all_calibration_values_checking_also_spelled_digits() {
         let input_file = read_input_file("example01b.txt");
         let expected = String::from("281");
-        assert_eq!(run(&input_file, true), expected.to_string());
+        assert_eq!(run(&input_file, true).unwrap(), expected.to_string());
+    }
+
+    #[test]
+    fn a_line_without_digits_returns_an_error() {
+        assert!(get_calibration_value("abcdef", true).is_err());
+        assert!(run(&[String::from("abc")], false).is_err());
+    }
+
+    #[test]
+    fn a_line_with_multi_byte_chars_does_not_panic() {
+        assert_eq!(get_calibration_value("é1ñtwo€", true).unwrap(), 12);
+        assert_eq!(get_calibration_value("€", true), Err("No digit found"));
+    }
+
+    #[test]
+    fn spelled_digits_next_to_accented_letters_are_found() {
+        assert_eq!(get_first_digit("àéîõütwo3", true).unwrap(), "2");
+        assert_eq!(get_last_digit("1sevenñ", true).unwrap(), "7");
+        assert_eq!(get_last_digit("1seve\u{301}n", true).unwrap(), "1");
+    }
+
+    #[test]
+    fn spelled_digits_next_to_emoji_are_found() {
+        assert_eq!(get_first_digit("😀nine👨\u{200D}👩\u{200D}👧", true).unwrap(), "9");
+        assert_eq!(get_last_digit("😀nine👨\u{200D}👩\u{200D}👧", true).unwrap(), "9");
+        assert_eq!(get_calibration_value("🎄4🎁five🎅", true).unwrap(), 45);
+        assert_eq!(get_calibration_value("🎄4🎁five🎅", false).unwrap(), 44);
     }
 }
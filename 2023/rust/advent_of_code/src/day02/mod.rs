@@ -1,25 +1,25 @@
-pub fn run_a(input: &Vec<String>, rules: &GameRules) -> String {
+pub fn run_a(input: &[String], rules: &GameRules) -> Result<String, &'static str> {
     let mut result = 0;
 
     for game_input in input {
-        let game = Game::new(game_input.to_string());
+        let game = Game::new(game_input.to_string())?;
         if game.is_possible(rules) {
-            result += game.id();
+            result += game.id() as u64;
         }
     }
 
-    result.to_string()
+    Ok(result.to_string())
 }
 
-pub fn run_b(input: &Vec<String>) -> String {
-    let mut result = 0;
+pub fn run_b(input: &[String]) -> Result<String, &'static str> {
+    let mut result = 0u64;
 
     for game_input in input {
-        let game = Game::new(game_input.to_string());
-        result += game.power();
+        let game = Game::new(game_input.to_string())?;
+        result = result.checked_add(game.power()?).ok_or("Sum of game powers overflows")?;
     }
 
-    result.to_string()
+    Ok(result.to_string())
 }
 
 pub struct GameRules {
@@ -28,35 +28,42 @@ pub struct GameRules {
     pub blue: u32,
 }
 
+const CUBE_COLORS: [&str; 3] = ["red", "green", "blue"];
+
 struct Game {
-    game: String,
+    id: u32,
+    sets: Vec<String>,
 }
 
 impl Game {
-    fn new(game: String) -> Game {
-        Game { game }
+    fn new(game: String) -> Result<Game, &'static str> {
+        let (header, sets) = game.split_once(':').ok_or("Missing game sets")?;
+
+        let id = header
+            .strip_prefix("Game ")
+            .ok_or("Missing game id")?
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| "Invalid game id")?;
+
+        let sets = sets
+            .split(';')
+            .map(|s| s.trim().to_string())
+            .collect::<Vec<String>>();
+
+        for set in sets.iter() {
+            validate_set(set)?;
+        }
+
+        Ok(Game { id, sets })
     }
 
     fn id(&self) -> u32 {
-        self.game
-            .split(':')
-            .next()
-            .unwrap()
-            .split(' ')
-            .nth(1)
-            .unwrap()
-            .parse::<u32>()
-            .unwrap()
+        self.id
     }
 
     fn sets(&self) -> Vec<&str> {
-        self.game
-            .split(':')
-            .nth(1)
-            .unwrap()
-            .split(';')
-            .map(|s| s.trim())
-            .collect()
+        self.sets.iter().map(|s| s.as_str()).collect()
     }
 
     fn is_possible(&self, rules: &GameRules) -> bool {
@@ -117,10 +124,11 @@ impl Game {
         minimum
     }
 
-    fn power(&self) -> u32 {
-        self.get_minimum_needed_green_cubes() *
-            self.get_minimum_needed_red_cubes() *
-            self.get_minimum_needed_blue_cubes()
+    fn power(&self) -> Result<u64, &'static str> {
+        (self.get_minimum_needed_green_cubes() as u64)
+            .checked_mul(self.get_minimum_needed_red_cubes() as u64)
+            .and_then(|power| power.checked_mul(self.get_minimum_needed_blue_cubes() as u64))
+            .ok_or("Game power overflows")
     }
 }
 
@@ -137,20 +145,27 @@ fn get_number_of_blue_cubes(set: &str) -> u32 {
 }
 
 fn get_number_of_cubes(set: &str, color: &str) -> u32 {
-    if !set.contains(color) {
-        return 0;
-    }
-
     set
         .split(',')
-        .find(|s| s.trim().ends_with(color))
-        .unwrap()
-        .trim()
-        .split(' ')
-        .next()
-        .unwrap()
-        .parse::<u32>()
-        .unwrap()
+        .filter_map(|cubes| cubes.trim().split_once(' '))
+        .find(|&(_, c)| c == color)
+        .and_then(|(number, _)| number.parse::<u32>().ok())
+        .unwrap_or(0)
+}
+
+// Checks that every entry of the set has the `<number> <color>` format
+fn validate_set(set: &str) -> Result<(), &'static str> {
+    for cubes in set.split(',') {
+        let (number, color) = cubes.trim().split_once(' ').ok_or("Invalid set of cubes")?;
+
+        number.parse::<u32>().map_err(|_| "Invalid number of cubes")?;
+
+        if !CUBE_COLORS.contains(&color) {
+            return Err("Invalid cube color");
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -161,7 +176,7 @@ mod tests {
     #[test]
     fn it_gets_the_correct_game_id() {
         let input = String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
-        let game = Game::new(input);
+        let game = Game::new(input).unwrap();
 
         let expected = 1;
         assert_eq!(game.id(), expected);
@@ -170,9 +185,9 @@ mod tests {
     #[test]
     fn it_splits_every_game_into_sets_of_cubes() {
         let input = String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
-        let game = Game::new(input);
+        let game = Game::new(input).unwrap();
 
-        let expected: Vec<String> = vec!["3 blue, 4 red", "1 red, 2 green, 6 blue", "2 green"]
+        let expected: Vec<String> = ["3 blue, 4 red", "1 red, 2 green, 6 blue", "2 green"]
             .iter()
             .map(|s| s.to_string())
             .collect();
@@ -216,7 +231,7 @@ mod tests {
     #[test]
     fn given_some_game_rules_is_able_to_determine_if_the_game_is_possible() {
         let input = String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
-        let game = Game::new(input);
+        let game = Game::new(input).unwrap();
 
         let rules = GameRules {
             red: 12,
@@ -230,7 +245,7 @@ mod tests {
     #[test]
     fn given_some_game_rules_is_able_to_determine_if_the_game_is_not_possible() {
         let input = String::from("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red");
-        let game = Game::new(input);
+        let game = Game::new(input).unwrap();
 
         let rules = GameRules {
             red: 12,
@@ -252,13 +267,13 @@ mod tests {
         };
 
         let expected = 8;
-        assert_eq!(run_a(&input, &rules), expected.to_string());
+        assert_eq!(run_a(&input, &rules).unwrap(), expected.to_string());
     }
 
     #[test]
     fn given_a_game_is_possible_to_get_the_minimum_number_of_green_cubes_needed_so_its_possible() {
         let input = String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
-        let game = Game::new(input);
+        let game = Game::new(input).unwrap();
 
         let expected = 2;
         assert_eq!(game.get_minimum_needed_green_cubes(), expected);
@@ -267,7 +282,7 @@ mod tests {
     #[test]
     fn given_a_game_is_possible_to_get_the_minimum_number_of_red_cubes_needed_so_its_possible() {
         let input = String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
-        let game = Game::new(input);
+        let game = Game::new(input).unwrap();
 
         let expected = 4;
         assert_eq!(game.get_minimum_needed_red_cubes(), expected);
@@ -276,7 +291,7 @@ mod tests {
     #[test]
     fn given_a_game_is_possible_to_get_the_minimum_number_of_blue_cubes_needed_so_its_possible() {
         let input = String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
-        let game = Game::new(input);
+        let game = Game::new(input).unwrap();
 
         let expected = 6;
         assert_eq!(game.get_minimum_needed_blue_cubes(), expected);
@@ -285,10 +300,10 @@ mod tests {
     #[test]
     fn given_a_game_is_possible_to_get_the_power_of_it() {
         let input = String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
-        let game = Game::new(input);
+        let game = Game::new(input).unwrap();
 
         let expected = 48;
-        assert_eq!(game.power(), expected);
+        assert_eq!(game.power().unwrap(), expected);
     }
 
     #[test]
@@ -296,6 +311,23 @@ mod tests {
         let input = read_input_file("example02a.txt");
 
         let expected = 2286;
-        assert_eq!(run_b(&input), expected.to_string());
+        assert_eq!(run_b(&input).unwrap(), expected.to_string());
+    }
+
+    #[test]
+    fn given_a_malformed_game_it_returns_an_error() {
+        assert_eq!(Game::new(String::from("Game 1 3 blue")).err(), Some("Missing game sets"));
+        assert_eq!(Game::new(String::from("Game x: 3 blue")).err(), Some("Invalid game id"));
+        assert_eq!(Game::new(String::from("Game 1: 3 reddish")).err(), Some("Invalid cube color"));
+        assert_eq!(Game::new(String::from("Game 1: 3blue")).err(), Some("Invalid set of cubes"));
+        assert_eq!(Game::new(String::from("Game 1: 99999999999 blue")).err(), Some("Invalid number of cubes"));
+    }
+
+    #[test]
+    fn given_a_game_with_huge_numbers_of_cubes_its_power_does_not_overflow_silently() {
+        let input = String::from("Game 1: 4294967295 red, 4294967295 green, 4294967295 blue");
+        let game = Game::new(input).unwrap();
+
+        assert!(game.power().is_err());
     }
 }
@@ -1,62 +1,62 @@
-pub fn run_a(input: &Vec<String>) -> String {
-    let mut result = 0;
+use unicode_segmentation::UnicodeSegmentation;
 
-    let (parts, symbols) = get_parts_and_symbols(input);
+// The schematic is a grid of extended grapheme clusters: every user-perceived character (an
+// accented letter, an emoji...) takes exactly one column, no matter how many bytes or chars it has.
+
+pub fn run_a(input: &[String]) -> Result<String, &'static str> {
+    let mut result = 0u64;
+
+    let (parts, symbols) = get_parts_and_symbols(input)?;
 
     for part in parts.iter() {
         for symbol in symbols.iter() {
             if part.is_adjacent_to_symbol(symbol) {
-                result += part.value;
+                result += part.value as u64;
             }
         }
     }
 
-    result.to_string()
+    Ok(result.to_string())
 }
 
-pub fn run_b(input: &Vec<String>) -> String {
-    let mut result = 0;
+pub fn run_b(input: &[String]) -> Result<String, &'static str> {
+    let mut result = 0u64;
 
-    let (parts, symbols) = get_parts_and_symbols(input);
+    let (parts, symbols) = get_parts_and_symbols(input)?;
 
     for symbol in symbols.iter() {
         if !symbol.is_gear() {
             continue;
         }
 
-        let mut number_of_adjacent_parts = 0;
-        let mut gear_ratio = 1;
+        let adjacent_parts = parts
+            .iter()
+            .filter(|part| part.is_adjacent_to_symbol(symbol))
+            .collect::<Vec<&Part>>();
 
-        for part in parts.iter() {
-            if part.is_adjacent_to_symbol(symbol) {
-                number_of_adjacent_parts += 1;
-                gear_ratio *= part.value;
-            }
-        }
-
-        if number_of_adjacent_parts != 2 {
+        if adjacent_parts.len() != 2 {
             continue;
         }
 
-        result += gear_ratio;
+        result += adjacent_parts[0].value as u64 * adjacent_parts[1].value as u64;
     }
 
-    result.to_string()
+    Ok(result.to_string())
 }
 
-fn get_parts_and_symbols(input: &Vec<String>) -> (Vec<Part>, Vec<Symbol>) {
+fn get_parts_and_symbols(input: &[String]) -> Result<(Vec<Part>, Vec<Symbol>), &'static str> {
     let mut parts: Vec<Part> = Vec::new();
     let mut symbols: Vec<Symbol> = Vec::new();
 
     for (i, line) in input.iter().enumerate() {
-        let parts_in_line = extract_parts_from_row(i, line);
+        let parts_in_line = extract_parts_from_row(i, line)?;
         parts.extend(parts_in_line);
 
         let symbols_in_line = extract_symbols_from_row(i, line);
         symbols.extend(symbols_in_line);
     }
 
-    (parts, symbols)
+    Ok((parts, symbols))
 }
 
 #[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
@@ -95,22 +95,22 @@ impl Point {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 struct Symbol {
-    value: char,
+    value: String,
     position: Point,
 }
 
 impl Symbol {
-    fn new() -> Symbol {
+    fn new(value: &str, position: Point) -> Symbol {
         Symbol {
-            value: ' ',
-            position: Point::default(),
+            value: value.to_string(),
+            position,
         }
     }
 
     fn is_gear(&self) -> bool {
-        self.value == '*'
+        self.value == "*"
     }
 }
 
@@ -145,7 +145,7 @@ impl Part {
     }
 }
 
-fn extract_parts_from_row(row: usize, input: &str) -> Vec<Part> {
+fn extract_parts_from_row(row: usize, input: &str) -> Result<Vec<Part>, &'static str> {
     let mut parts = Vec::new();
 
     let mut number = String::new();
@@ -155,39 +155,40 @@ fn extract_parts_from_row(row: usize, input: &str) -> Vec<Part> {
     // Add a dot at the end of the input to make sure the last number is added to the parts
     let input = format!("{}.", input);
 
-    for (i, c) in input.chars().enumerate() {
-        if c.is_ascii_digit() {
+    for (i, grapheme) in input.graphemes(true).enumerate() {
+        if is_digit(grapheme) {
             if number.is_empty() {
                 part.start = Point::new(i, row);
             }
-            number.push(c);
+            number.push_str(grapheme);
         } else if !number.is_empty() {
             part.end = Point::new(i - 1, row);
-            part.value = number.parse::<u32>().unwrap();
+            part.value = number.parse::<u32>().map_err(|_| "Part number too big")?;
             parts.push(part);
             number.clear();
         }
     }
 
-    parts
+    Ok(parts)
 }
 
 fn extract_symbols_from_row(row: usize, input: &str) -> Vec<Symbol> {
     let mut symbols = Vec::new();
 
-    let mut symbol = Symbol::new();
-
-    input.chars().enumerate().for_each(|(i, c)| {
-        if !c.is_ascii_digit() && c != '.' {
-            symbol.value = c;
-            symbol.position = Point::new(i, row);
-            symbols.push(symbol);
+    input.graphemes(true).enumerate().for_each(|(i, grapheme)| {
+        if !is_digit(grapheme) && grapheme != "." {
+            symbols.push(Symbol::new(grapheme, Point::new(i, row)));
         }
     });
 
     symbols
 }
 
+// A digit followed by a combining mark is a single grapheme, but it is not a digit anymore
+fn is_digit(grapheme: &str) -> bool {
+    grapheme.len() == 1 && grapheme.as_bytes()[0].is_ascii_digit()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,7 +198,7 @@ mod tests {
     fn given_an_input_line_it_identifies_all_the_parts() {
         let input = "467..114..";
 
-        let parts = extract_parts_from_row(0, input);
+        let parts = extract_parts_from_row(0, input).unwrap();
 
         assert_eq!(parts.len(), 2);
     }
@@ -206,7 +207,7 @@ mod tests {
     fn given_an_input_line_it_returns_the_parts_with_their_value_and_position() {
         let input = "467....114";
 
-        let parts = extract_parts_from_row(0, input);
+        let parts = extract_parts_from_row(0, input).unwrap();
 
         assert_eq!(parts[0].value, 467);
         assert_eq!(parts[0].start, Point::new(0, 0));
@@ -230,14 +231,14 @@ mod tests {
         let input = "617*......";
         let symbols = extract_symbols_from_row(0, input);
 
-        assert_eq!(symbols[0].value, '*');
+        assert_eq!(symbols[0].value, "*");
         assert_eq!(symbols[0].position, Point::new(3, 0));
     }
 
     #[test]
     fn given_a_symbol_it_knows_if_its_a_gear() {
         let symbol = Symbol {
-            value: '*',
+            value: String::from("*"),
             position: Point::default(),
         };
 
@@ -299,7 +300,7 @@ mod tests {
         };
 
         let symbol = Symbol {
-            value: '*',
+            value: String::from("*"),
             position: Point::new(5, 8),
         };
 
@@ -311,7 +312,7 @@ mod tests {
         let input = read_input_file("example03a.txt");
 
         let expected = 4361;
-        assert_eq!(run_a(&input), expected.to_string());
+        assert_eq!(run_a(&input).unwrap(), expected.to_string());
     }
 
     #[test]
@@ -319,6 +320,60 @@ mod tests {
         let input = read_input_file("example03a.txt");
 
         let expected = 467835;
-        assert_eq!(run_b(&input), expected.to_string());
+        assert_eq!(run_b(&input).unwrap(), expected.to_string());
+    }
+
+    #[test]
+    fn given_an_input_line_with_a_part_number_too_big_it_returns_an_error() {
+        let input = "..99999999999..";
+
+        assert!(extract_parts_from_row(0, input).is_err());
+    }
+
+    #[test]
+    fn given_a_gear_between_two_huge_parts_its_ratio_does_not_overflow() {
+        let input = vec![String::from("4000000000*4000000000")];
+
+        assert_eq!(run_b(&input).unwrap(), "16000000000000000000");
+    }
+
+    #[test]
+    fn given_an_input_line_with_multi_byte_graphemes_every_one_of_them_is_a_single_column() {
+        let input = "é😀*ñ12";
+
+        let symbols = extract_symbols_from_row(0, input);
+        let parts = extract_parts_from_row(0, input).unwrap();
+
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(symbols[1].value, "😀");
+        assert_eq!(symbols[2].position, Point::new(2, 0));
+        assert_eq!(parts[0].start, Point::new(4, 0));
+        assert_eq!(parts[0].end, Point::new(5, 0));
+    }
+
+    #[test]
+    fn given_an_input_line_with_combining_marks_and_emoji_sequences_they_take_one_column() {
+        // "e" + combining acute accent and a family emoji joined with zero width joiners
+        let input = "e\u{301}\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}*3\u{301}";
+
+        let symbols = extract_symbols_from_row(0, input);
+        let parts = extract_parts_from_row(0, input).unwrap();
+
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(symbols[2].value, "*");
+        assert_eq!(symbols[2].position, Point::new(2, 0));
+        assert!(parts.is_empty());
+    }
+
+    #[test]
+    fn given_a_schematic_with_multi_byte_graphemes_parts_and_gears_stay_aligned() {
+        let input = vec![
+            String::from("é😀.467"),
+            String::from("ñ..*.."),
+            String::from("..35.."),
+        ];
+
+        assert_eq!(run_a(&input).unwrap(), "502");
+        assert_eq!(run_b(&input).unwrap(), "16345");
     }
 }
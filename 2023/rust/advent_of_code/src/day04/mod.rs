@@ -1,41 +1,59 @@
 use std::collections::HashMap;
 
-pub fn run_a(input: &Vec<String>) -> String {
-    let mut result = 0;
+pub fn run_a(input: &[String]) -> Result<String, &'static str> {
+    let mut result = 0u64;
 
     for line in input.iter() {
-        let card_values = line.split(':').last().unwrap().trim();
-        let scratchcard = Scratchcard::from(card_values);
-        result += scratchcard.value();
+        let (_, card_values) = split_card(line)?;
+        let scratchcard = Scratchcard::try_from(card_values)?;
+        result = result.checked_add(scratchcard.value()?).ok_or("Sum of scratchcard values overflows")?;
     }
 
-    result.to_string()
+    Ok(result.to_string())
 }
 
-pub fn run_b(input: &Vec<String>) -> String {
-    let mut result = 0;
+pub fn run_b(input: &[String]) -> Result<String, &'static str> {
+    let mut result = 0u64;
 
-
-    let mut number_of_copies: HashMap<u32, u32> = HashMap::new();
+    let mut number_of_copies: HashMap<u32, u64> = HashMap::new();
 
     for line in input.iter() {
-        let card_number = line.split(':').next().unwrap().trim().split(' ').last().unwrap().parse::<u32>().unwrap();
-        let card_values = line.split(':').last().unwrap().trim();
-        let scratchcard = Scratchcard::from(card_values);
+        let (card_number, card_values) = split_card(line)?;
+        let scratchcard = Scratchcard::try_from(card_values)?;
         let winning_numbers = scratchcard.number_of_matches();
 
         // Get current card copies
         let card_copies = *number_of_copies.get(&card_number).unwrap_or(&1);
-        result += card_copies;
+        result = result.checked_add(card_copies).ok_or("Too many scratchcards")?;
 
         // Add new copies
-        for cn in card_number + 1..=(card_number + winning_numbers) {
-            let new_copies = number_of_copies.get(&cn).unwrap_or(&1) + card_copies;
+        let last_card_number = card_number.checked_add(winning_numbers).ok_or("Card number too big")?;
+        for cn in card_number + 1..=last_card_number {
+            let new_copies = number_of_copies
+                .get(&cn)
+                .unwrap_or(&1)
+                .checked_add(card_copies)
+                .ok_or("Too many scratchcards")?;
             number_of_copies.insert(cn, new_copies);
         }
     }
 
-    result.to_string()
+    Ok(result.to_string())
+}
+
+// Splits a `Card <number>: <values>` line into the card number and its values
+fn split_card(line: &str) -> Result<(u32, &str), &'static str> {
+    let (card, card_values) = line.split_once(':').ok_or("Missing card numbers")?;
+
+    let card_number = card
+        .trim()
+        .strip_prefix("Card")
+        .ok_or("Missing card number")?
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| "Invalid card number")?;
+
+    Ok((card_number, card_values.trim()))
 }
 
 struct Scratchcard {
@@ -43,17 +61,19 @@ struct Scratchcard {
     scratched_numbers: Vec<u32>,
 }
 
-impl From<&str> for Scratchcard {
-    fn from(input: &str) -> Self {
-        let mut numbers = input.split('|');
+impl TryFrom<&str> for Scratchcard {
+    type Error = &'static str;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let (winning_numbers, scratched_numbers) = input.split_once('|').ok_or("Missing scratched numbers")?;
 
-        let winning_numbers = num_str_to_vec(numbers.next().unwrap());
-        let scratched_numbers = num_str_to_vec(numbers.next().unwrap());
+        let winning_numbers = num_str_to_vec(winning_numbers)?;
+        let scratched_numbers = num_str_to_vec(scratched_numbers)?;
 
-        Scratchcard {
+        Ok(Scratchcard {
             winning_numbers,
             scratched_numbers,
-        }
+        })
     }
 }
 
@@ -70,28 +90,28 @@ impl Scratchcard {
         matches
     }
 
-    fn value(&self) -> u32 {
-        let mut value = 0;
+    fn value(&self) -> Result<u64, &'static str> {
+        let mut value = 0u64;
 
         for number in &self.scratched_numbers {
             if self.winning_numbers.contains(number) {
                 if value == 0 {
                     value = 1;
                 } else {
-                    value *= 2;
+                    value = value.checked_mul(2).ok_or("Scratchcard value overflows")?;
                 }
             }
         }
 
-        value
+        Ok(value)
     }
 }
 
-fn num_str_to_vec(input: &str) -> Vec<u32> {
+fn num_str_to_vec(input: &str) -> Result<Vec<u32>, &'static str> {
     input
         .split(' ')
         .filter(|n| !n.is_empty())
-        .map(|n| n.parse::<u32>().unwrap())
+        .map(|n| n.parse::<u32>().map_err(|_| "Invalid number"))
         .collect()
 }
 
@@ -103,12 +123,12 @@ mod tests {
     #[test]
     fn given_a_string_with_a_list_of_numbers_is_possible_to_get_the_actual_numbers() {
         let input = "   1 2 3 4    5";
-        assert_eq!(num_str_to_vec(input), vec![1, 2, 3, 4, 5]);
+        assert_eq!(num_str_to_vec(input).unwrap(), vec![1, 2, 3, 4, 5]);
     }
 
     #[test]
     fn given_an_input_it_gets_the_scratchcard_with_scratched_and_winning_numbers() {
-        let scratchcard = Scratchcard::from("41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let scratchcard = Scratchcard::try_from("41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
 
         assert_eq!(scratchcard.winning_numbers, vec![41, 48, 83, 86, 17]);
         assert_eq!(scratchcard.scratched_numbers, vec![83, 86, 6, 31, 17, 9, 48, 53]);
@@ -116,29 +136,44 @@ mod tests {
 
     #[test]
     fn given_a_scratchcard_is_possible_to_get_its_number_of_matches() {
-        let scratchcard = Scratchcard::from("41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let scratchcard = Scratchcard::try_from("41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
 
         assert_eq!(scratchcard.number_of_matches(), 4);
     }
 
     #[test]
     fn given_a_scratchcard_is_possible_to_get_its_value() {
-        let scratchcard = Scratchcard::from("41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let scratchcard = Scratchcard::try_from("41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
 
-        assert_eq!(scratchcard.value(), 8);
+        assert_eq!(scratchcard.value().unwrap(), 8);
     }
 
     #[test]
     fn given_a_pile_of_cards_is_possible_to_get_the_total_value() {
         let input = read_input_file("example04a.txt");
 
-        assert_eq!(run_a(&input), "13");
+        assert_eq!(run_a(&input).unwrap(), "13");
     }
 
     #[test]
     fn given_a_pile_of_cards_and_the_new_instructions_is_possible_to_get_the_total_value() {
         let input = read_input_file("example04a.txt");
 
-        assert_eq!(run_b(&input), "30");
+        assert_eq!(run_b(&input).unwrap(), "30");
+    }
+
+    #[test]
+    fn given_a_malformed_card_it_returns_an_error() {
+        assert_eq!(split_card("Card 1 41 48").err(), Some("Missing card numbers"));
+        assert_eq!(split_card("Card x: 41 | 48").err(), Some("Invalid card number"));
+        assert_eq!(Scratchcard::try_from("41 48 83").err(), Some("Missing scratched numbers"));
+        assert_eq!(Scratchcard::try_from("41 4x | 83").err(), Some("Invalid number"));
+    }
+
+    #[test]
+    fn given_a_card_with_the_last_possible_number_it_does_not_overflow() {
+        let input = vec![String::from("Card 4294967295: 1 | 1")];
+
+        assert!(run_b(&input).is_err());
     }
 }
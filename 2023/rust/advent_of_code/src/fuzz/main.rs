@@ -0,0 +1,48 @@
+use std::env;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use advent_of_code::fuzz;
+
+const DEFAULT_ITERATIONS: usize = 100_000;
+
+fn main() {
+    let args = env::args().collect::<Vec<String>>();
+
+    let targets = match args.get(1).map(|name| name.as_str()) {
+        None | Some("all") => fuzz::TARGETS.iter().collect::<Vec<&fuzz::Target>>(),
+        Some(name) => match fuzz::find_target(name) {
+            Some(target) => vec![target],
+            None => {
+                eprintln!("Unknown fuzz target: {}", name);
+                process::exit(2);
+            }
+        },
+    };
+
+    let iterations = args
+        .get(2)
+        .map(|n| n.parse::<usize>().expect("Invalid number of iterations"))
+        .unwrap_or(DEFAULT_ITERATIONS);
+
+    let seed = args
+        .get(3)
+        .map(|n| n.parse::<u64>().expect("Invalid seed"))
+        .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs());
+
+    let mut crashed = false;
+
+    for target in targets {
+        println!("Fuzzing {} with {} inputs (seed {})", target.name, iterations, seed);
+
+        if let Err(data) = fuzz::fuzz(target, iterations, seed) {
+            println!("Crash found in {} with input: {:?}", target.name, String::from_utf8_lossy(&data));
+            advent_of_code::write_output_file(&format!("crash-{}.txt", target.name), &String::from_utf8_lossy(&data));
+            crashed = true;
+        }
+    }
+
+    if crashed {
+        process::exit(1);
+    }
+}
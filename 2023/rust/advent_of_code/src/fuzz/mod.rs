@@ -0,0 +1,152 @@
+use std::panic;
+
+use crate::{day01, day02, day03, day04, parse_input};
+
+// Longest input generated, in tokens
+const MAX_INPUT_TOKENS: usize = 64;
+
+// Pieces of the puzzle inputs, mixed with random bytes so the generated inputs get past the first
+// checks of every parser
+const DICTIONARY: [&str; 27] = [
+    "Game ", "Card ", ":", ": ", ";", ",", " | ", " ", "\n", "red", "green", "blue", "one", "two",
+    "eight", "nine", "*", ".", "#", "0", "1", "7", "4294967295", "99999999999", "é", "€", "😀",
+];
+
+pub struct Target {
+    pub name: &'static str,
+    pub run: fn(&[String]),
+}
+
+pub const TARGETS: [Target; 4] = [
+    Target { name: "day01", run: fuzz_day01 },
+    Target { name: "day02", run: fuzz_day02 },
+    Target { name: "day03", run: fuzz_day03 },
+    Target { name: "day04", run: fuzz_day04 },
+];
+
+fn fuzz_day01(input: &[String]) {
+    let _ = day01::run(input, false);
+    let _ = day01::run(input, true);
+}
+
+fn fuzz_day02(input: &[String]) {
+    let rules = day02::GameRules {
+        red: 12,
+        green: 13,
+        blue: 14,
+    };
+
+    let _ = day02::run_a(input, &rules);
+    let _ = day02::run_b(input);
+}
+
+fn fuzz_day03(input: &[String]) {
+    let _ = day03::run_a(input);
+    let _ = day03::run_b(input);
+}
+
+fn fuzz_day04(input: &[String]) {
+    let _ = day04::run_a(input);
+    let _ = day04::run_b(input);
+}
+
+pub fn find_target(name: &str) -> Option<&'static Target> {
+    TARGETS.iter().find(|target| target.name == name)
+}
+
+// Xorshift generator, good enough to produce inputs and reproducible from its seed
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // The state of a xorshift generator must never be zero
+        Rng { state: seed.max(1) }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    fn below(&mut self, max: usize) -> usize {
+        (self.next() % max as u64) as usize
+    }
+}
+
+pub fn generate_input(rng: &mut Rng) -> Vec<u8> {
+    let mut input = Vec::new();
+
+    for _ in 0..rng.below(MAX_INPUT_TOKENS) {
+        if rng.below(2) == 0 {
+            input.extend_from_slice(DICTIONARY[rng.below(DICTIONARY.len())].as_bytes());
+        } else {
+            input.push(rng.below(256) as u8);
+        }
+    }
+
+    input
+}
+
+// Runs the target with the given bytes and returns if it finished without panicking
+pub fn run_target(target: &Target, data: &[u8]) -> bool {
+    let input = parse_input(&String::from_utf8_lossy(data));
+
+    panic::catch_unwind(|| (target.run)(&input)).is_ok()
+}
+
+// Feeds the target with random inputs and returns the first one that made it panic
+pub fn fuzz(target: &Target, iterations: usize, seed: u64) -> Result<(), Vec<u8>> {
+    let mut rng = Rng::new(seed);
+
+    for _ in 0..iterations {
+        let data = generate_input(&mut rng);
+        if !run_target(target, &data) {
+            return Err(data);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_generates_the_same_inputs() {
+        let mut rng_a = Rng::new(42);
+        let mut rng_b = Rng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(generate_input(&mut rng_a), generate_input(&mut rng_b));
+        }
+    }
+
+    #[test]
+    fn a_target_can_be_found_by_its_name() {
+        assert_eq!(find_target("day03").unwrap().name, "day03");
+        assert!(find_target("day99").is_none());
+    }
+
+    #[test]
+    fn a_panicking_target_is_detected() {
+        let target = Target {
+            name: "panic",
+            run: |input| assert!(input.is_empty()),
+        };
+
+        assert!(!run_target(&target, b"not empty"));
+        assert!(fuzz(&target, 1000, 1).is_err());
+    }
+
+    #[test]
+    fn no_parser_panics_with_random_inputs() {
+        for target in TARGETS.iter() {
+            assert_eq!(fuzz(target, 2000, 2023), Ok(()), "{} panicked", target.name);
+        }
+    }
+}
@@ -1,7 +1,11 @@
+pub mod cache;
 pub mod day01;
 pub mod day02;
 pub mod day03;
 pub mod day04;
+pub mod fuzz;
+pub mod report;
+pub mod runner;
 
 use std::fs;
 
@@ -10,9 +14,18 @@ pub fn read_input_file(path: &str) -> Vec<String> {
 
     let input = fs::read_to_string(full_path).expect("Cannot open input file");
 
+    parse_input(&input)
+}
+
+pub fn parse_input(input: &str) -> Vec<String> {
     input.trim().lines().map(String::from).collect()
 }
 
+pub fn read_output_file(path: &str) -> Option<String> {
+    let full_path = format!("output/{}", path);
+    fs::read_to_string(full_path).ok().map(|output| output.trim_end().to_string())
+}
+
 pub fn write_output_file(path: &str, data: &str) {
     let full_path = format!("output/{}", path);
     fs::write(full_path, data).expect("Cannot write output file");
@@ -29,6 +42,12 @@ mod tests {
         assert_eq!(input, expected_input)
     }
 
+    #[test]
+    fn the_example_output_file_can_be_read() {
+        assert_eq!(read_output_file("example01.txt"), Some(String::from("142")));
+        assert_eq!(read_output_file("does-not-exist.txt"), None);
+    }
+
     #[test]
     fn the_example_output_file_can_be_written() {
         let result = "This is a file example";
@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use crate::runner::{day_title, Run};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    // The answer matches the one stored in the output file
+    Verified,
+    // The answer differs from the one stored in the output file
+    Mismatch(String),
+    // There is no stored answer to compare with
+    Unverified,
+    Failed(String),
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Verified => "verified",
+            Status::Mismatch(_) => "mismatch",
+            Status::Unverified => "unverified",
+            Status::Failed(_) => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Option<String>,
+    pub duration: Option<Duration>,
+    pub cached: bool,
+    pub status: Status,
+}
+
+impl Record {
+    // Builds the record of a run, verifying its answer against the expected one if known
+    pub fn new(run: &Run, expected: Option<&str>) -> Record {
+        let (answer, duration, cached, status) = match &run.result {
+            Ok(answer) => {
+                let status = match expected {
+                    Some(expected) if expected == answer.answer => Status::Verified,
+                    Some(expected) => Status::Mismatch(expected.to_string()),
+                    None => Status::Unverified,
+                };
+                (Some(answer.answer.clone()), Some(answer.duration), answer.cached, status)
+            }
+            Err(error) => (None, None, false, Status::Failed(error.clone())),
+        };
+
+        Record {
+            day: run.solution.day,
+            part: run.solution.part,
+            answer,
+            duration,
+            cached,
+            status,
+        }
+    }
+}
+
+// One JSON object per record, with `null` answer and duration when the part failed
+pub fn to_json_line(record: &Record) -> String {
+    let answer = match &record.answer {
+        Some(answer) => json_string(answer),
+        None => String::from("null"),
+    };
+
+    let duration = match record.duration {
+        Some(duration) => duration.as_nanos().to_string(),
+        None => String::from("null"),
+    };
+
+    let detail = match &record.status {
+        Status::Mismatch(expected) => format!(",\"expected\":{}", json_string(expected)),
+        Status::Failed(error) => format!(",\"error\":{}", json_string(error)),
+        _ => String::new(),
+    };
+
+    format!(
+        "{{\"day\":{},\"part\":{},\"answer\":{},\"duration_ns\":{},\"cached\":{},\"status\":\"{}\"{}}}",
+        record.day,
+        record.part,
+        answer,
+        duration,
+        record.cached,
+        record.status.name(),
+        detail
+    )
+}
+
+// Table with a row per part, linking every day to its directory like the challenge list of the
+// README does
+pub fn to_markdown_table(records: &[Record]) -> String {
+    let mut table = String::from("| Day | Part | Answer | Time | Status |\n|---|---|---|---|---|\n");
+
+    for record in records {
+        let day = format!("[{}](src/day{:02})", day_title(record.day), record.day);
+        let answer = record.answer.as_deref().unwrap_or("-").replace('|', "\\|");
+        let duration = match record.duration {
+            Some(duration) => format!("{:.3} ms", duration.as_secs_f64() * 1000.0),
+            None => String::from("-"),
+        };
+
+        table.push_str(&format!(
+            "| {} | {} | `{}` | {} | {} |\n",
+            day,
+            record.part,
+            answer,
+            duration,
+            record.status.name()
+        ));
+    }
+
+    table
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{Answer, SOLUTIONS};
+
+    fn run(result: Result<&str, &str>) -> Run {
+        Run {
+            solution: &SOLUTIONS[2],
+            result: result
+                .map(|answer| Answer {
+                    day: 2,
+                    part: 1,
+                    input_hash: 0,
+                    answer: answer.to_string(),
+                    duration: Duration::from_micros(1500),
+                    cached: false,
+                })
+                .map_err(|error| error.to_string()),
+            started: Duration::ZERO,
+            finished: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn the_answer_of_a_run_is_verified_against_the_expected_one() {
+        assert_eq!(Record::new(&run(Ok("8")), Some("8")).status, Status::Verified);
+        assert_eq!(Record::new(&run(Ok("8")), Some("9")).status, Status::Mismatch(String::from("9")));
+        assert_eq!(Record::new(&run(Ok("8")), None).status, Status::Unverified);
+        assert_eq!(Record::new(&run(Err("boom")), Some("8")).status, Status::Failed(String::from("boom")));
+    }
+
+    #[test]
+    fn a_record_can_be_formatted_as_a_json_line() {
+        let record = Record::new(&run(Ok("8")), Some("8"));
+
+        assert_eq!(
+            to_json_line(&record),
+            r#"{"day":2,"part":1,"answer":"8","duration_ns":1500000,"cached":false,"status":"verified"}"#
+        );
+    }
+
+    #[test]
+    fn a_failed_record_is_formatted_as_a_json_line_with_its_error() {
+        let record = Record::new(&run(Err("Invalid \"game\"")), None);
+
+        assert_eq!(
+            to_json_line(&record),
+            r#"{"day":2,"part":1,"answer":null,"duration_ns":null,"cached":false,"status":"failed","error":"Invalid \"game\""}"#
+        );
+    }
+
+    #[test]
+    fn strings_are_escaped_in_json() {
+        assert_eq!(json_string("a\\b\n\u{1}é"), r#""a\\b\n\u0001é""#);
+    }
+
+    #[test]
+    fn records_can_be_formatted_as_a_markdown_table() {
+        let records = vec![
+            Record::new(&run(Ok("8")), Some("9")),
+            Record::new(&run(Err("boom")), None),
+        ];
+
+        assert_eq!(
+            to_markdown_table(&records),
+            "| Day | Part | Answer | Time | Status |\n\
+             |---|---|---|---|---|\n\
+             | [Day 2: Cube Conundrum](src/day02) | 1 | `8` | 1.500 ms | mismatch |\n\
+             | [Day 2: Cube Conundrum](src/day02) | 1 | `-` | - | failed |\n"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::env;
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Instant;
+
+use advent_of_code::cache::{Cache, CACHE_FILE};
+use advent_of_code::report::{self, Record, Status};
+use advent_of_code::runner::{self, Solution};
+
+const USAGE: &str = "Usage:
+    runner <day>|all [--force] [--accept] [--threads <n>] [--format text|json|markdown]
+                              Solves both parts of the day, or of every day, using the cached
+                              answers unless forced. Answers are verified against the output files,
+                              which are only overwritten by a different answer when accepted
+    runner cache list         Lists the cached answers
+    runner cache prune        Drops the stale cached answers";
+
+#[derive(PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Markdown,
+}
+
+struct Options {
+    force: bool,
+    accept: bool,
+    threads: usize,
+    format: Format,
+}
+
+fn main() {
+    let mut args = env::args().skip(1).collect::<Vec<String>>();
+    let options = parse_options(&mut args);
+    let mut cache = Cache::load(Path::new(CACHE_FILE));
+
+    match args.iter().map(|arg| arg.as_str()).collect::<Vec<&str>>()[..] {
+        ["cache", "list"] => list_cache(&cache),
+        ["cache", "prune"] => prune_cache(&mut cache),
+        ["all"] => run(runner::SOLUTIONS.iter().collect(), &mut cache, &options),
+        [day] => run(runner::solutions_of_day(parse_day(day)), &mut cache, &options),
+        _ => exit_with_usage(),
+    }
+}
+
+// Removes the flags from the arguments, leaving only the command
+fn parse_options(args: &mut Vec<String>) -> Options {
+    let mut options = Options {
+        force: false,
+        accept: false,
+        threads: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        format: Format::Text,
+    };
+
+    if let Some(i) = args.iter().position(|arg| arg == "--force") {
+        args.remove(i);
+        options.force = true;
+    }
+
+    if let Some(i) = args.iter().position(|arg| arg == "--accept") {
+        args.remove(i);
+        options.accept = true;
+    }
+
+    if let Some(i) = args.iter().position(|arg| arg == "--threads") {
+        args.remove(i);
+        if i >= args.len() {
+            exit_with_usage();
+        }
+        options.threads = args.remove(i).parse::<usize>().unwrap_or_else(|_| exit_with_usage());
+    }
+
+    if let Some(i) = args.iter().position(|arg| arg == "--format") {
+        args.remove(i);
+        if i >= args.len() {
+            exit_with_usage();
+        }
+        options.format = match args.remove(i).as_str() {
+            "text" => Format::Text,
+            "json" => Format::Json,
+            "markdown" => Format::Markdown,
+            _ => exit_with_usage(),
+        };
+    }
+
+    options
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+fn parse_day(day: &str) -> u32 {
+    match day.trim_start_matches("day").parse::<u32>() {
+        Ok(day) if !runner::solutions_of_day(day).is_empty() => day,
+        _ => {
+            eprintln!("Unknown day: {}", day);
+            process::exit(2);
+        }
+    }
+}
+
+fn run(solutions: Vec<&'static Solution>, cache: &mut Cache, options: &Options) {
+    let start = Instant::now();
+    let runs = runner::run_parallel(&solutions, cache, options.force, options.threads);
+    let total = start.elapsed();
+
+    let mut records = Vec::new();
+
+    for run in runs.iter() {
+        // The stored answer has to be read before it is overwritten by the new one
+        let record = Record::new(run, advent_of_code::read_output_file(&run.solution.output_file()).as_deref());
+
+        if let Ok(answer) = &run.result {
+            // A mismatch keeps being reported until the new answer is explicitly accepted
+            if !matches!(record.status, Status::Mismatch(_)) || options.accept {
+                advent_of_code::write_output_file(&run.solution.output_file(), &answer.answer);
+            }
+            runner::store(cache, answer);
+        }
+
+        match options.format {
+            Format::Text => print_record(&record),
+            Format::Json => println!("{}", report::to_json_line(&record)),
+            Format::Markdown => {}
+        }
+
+        records.push(record);
+    }
+
+    if options.format == Format::Markdown {
+        print!("{}", report::to_markdown_table(&records));
+    }
+
+    // Keep the structured output clean, the timings are already part of it
+    if options.format == Format::Text {
+        for (day, wall_time) in runner::wall_time_per_day(&runs) {
+            println!("Day {:02} wall time: {:?}", day, wall_time);
+        }
+        println!("Total wall time: {:?} ({} threads)", total, options.threads);
+    } else {
+        eprintln!("Total wall time: {:?} ({} threads)", total, options.threads);
+    }
+
+    let failed = records.iter().any(|record| match record.status {
+        Status::Failed(_) => true,
+        Status::Mismatch(_) => !options.accept,
+        _ => false,
+    });
+
+    cache.save().expect("Cannot write cache file");
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn print_record(record: &Record) {
+    match (&record.answer, &record.status) {
+        (Some(answer), status) => {
+            let cached = if record.cached { ", cached" } else { "" };
+            println!(
+                "Result day {:02} part {}: {} ({:?}{}, {})",
+                record.day,
+                record.part,
+                answer,
+                record.duration.unwrap_or_default(),
+                cached,
+                status.name()
+            );
+            if let Status::Mismatch(expected) = status {
+                eprintln!("Day {:02} part {} expected: {}", record.day, record.part, expected);
+            }
+        }
+        (None, Status::Failed(error)) => {
+            eprintln!("Day {:02} part {} failed: {}", record.day, record.part, error);
+        }
+        (None, _) => {}
+    }
+}
+
+fn list_cache(cache: &Cache) {
+    for (key, entry) in cache.entries() {
+        let stale = if runner::is_stale(key) { " (stale)" } else { "" };
+        println!(
+            "Day {:02} part {} [input {:016x}, v{}]: {} ({:?}){}",
+            key.day, key.part, key.input_hash, key.version, entry.answer, entry.duration, stale
+        );
+    }
+}
+
+fn prune_cache(cache: &mut Cache) {
+    let removed = cache.retain(|key| !runner::is_stale(key));
+    cache.save().expect("Cannot write cache file");
+
+    println!("Removed {} stale cache entries", removed);
+}
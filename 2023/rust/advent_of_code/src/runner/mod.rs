@@ -0,0 +1,368 @@
+use std::any::Any;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cache::{Cache, CacheEntry, CacheKey, VERSION};
+use crate::{day01, day02, day03, day04, parse_input};
+
+const GAME_RULES: day02::GameRules = day02::GameRules {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    pub solve: fn(&[String]) -> Result<String, &'static str>,
+}
+
+impl Solution {
+    pub fn input_file(&self) -> String {
+        format!("input{:02}.txt", self.day)
+    }
+
+    pub fn output_file(&self) -> String {
+        // Part one is stored as `a` and part two as `b`
+        let part = if self.part == 1 { 'a' } else { 'b' };
+        format!("output{:02}{}.txt", self.day, part)
+    }
+}
+
+pub const SOLUTIONS: [Solution; 8] = [
+    Solution { day: 1, part: 1, solve: |input| day01::run(input, false) },
+    Solution { day: 1, part: 2, solve: |input| day01::run(input, true) },
+    Solution { day: 2, part: 1, solve: |input| day02::run_a(input, &GAME_RULES) },
+    Solution { day: 2, part: 2, solve: day02::run_b },
+    Solution { day: 3, part: 1, solve: day03::run_a },
+    Solution { day: 3, part: 2, solve: day03::run_b },
+    Solution { day: 4, part: 1, solve: day04::run_a },
+    Solution { day: 4, part: 2, solve: day04::run_b },
+];
+
+pub fn day_title(day: u32) -> &'static str {
+    match day {
+        1 => "Day 1: Trebuchet?!",
+        2 => "Day 2: Cube Conundrum",
+        3 => "Day 3: Gear Ratios",
+        4 => "Day 4: Scratchcards",
+        _ => "Unknown day",
+    }
+}
+
+pub fn solutions_of_day(day: u32) -> Vec<&'static Solution> {
+    SOLUTIONS.iter().filter(|solution| solution.day == day).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub day: u32,
+    pub part: u32,
+    pub input_hash: u64,
+    pub answer: String,
+    pub duration: Duration,
+    pub cached: bool,
+}
+
+// Solves the part, returning the cached answer for the same input and crate version unless forced.
+// New answers are not added to the cache, see `store`.
+pub fn solve(solution: &Solution, input: &[String], cache: &Cache, force: bool) -> Result<Answer, &'static str> {
+    let key = CacheKey::new(solution.day, solution.part, input);
+
+    if !force {
+        if let Some(entry) = cache.get(&key) {
+            return Ok(Answer {
+                day: solution.day,
+                part: solution.part,
+                input_hash: key.input_hash,
+                answer: entry.answer.clone(),
+                duration: entry.duration,
+                cached: true,
+            });
+        }
+    }
+
+    let start = Instant::now();
+    let answer = (solution.solve)(input)?;
+    let duration = start.elapsed();
+
+    Ok(Answer {
+        day: solution.day,
+        part: solution.part,
+        input_hash: key.input_hash,
+        answer,
+        duration,
+        cached: false,
+    })
+}
+
+pub fn store(cache: &mut Cache, answer: &Answer) {
+    let key = CacheKey {
+        day: answer.day,
+        part: answer.part,
+        input_hash: answer.input_hash,
+        version: VERSION.to_string(),
+    };
+
+    cache.insert(
+        key,
+        CacheEntry {
+            answer: answer.answer.clone(),
+            duration: answer.duration,
+        },
+    );
+}
+
+pub struct Run {
+    pub solution: &'static Solution,
+    pub result: Result<Answer, String>,
+    // Offsets since the beginning of the whole execution
+    pub started: Duration,
+    pub finished: Duration,
+}
+
+// Solves every part on a pool of threads and returns the runs in the same order as the solutions.
+// A part that fails or panics is reported as an error without affecting the rest.
+pub fn run_parallel(solutions: &[&'static Solution], cache: &Cache, force: bool, threads: usize) -> Vec<Run> {
+    let start = Instant::now();
+    let next_solution = AtomicUsize::new(0);
+    let runs = Mutex::new((0..solutions.len()).map(|_| None).collect::<Vec<Option<Run>>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, solutions.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next_solution.fetch_add(1, Ordering::Relaxed);
+                if i >= solutions.len() {
+                    break;
+                }
+
+                let run = run_isolated(solutions[i], cache, force, start);
+                runs.lock().unwrap()[i] = Some(run);
+            });
+        }
+    });
+
+    runs.into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|run| run.expect("Every solution is run"))
+        .collect()
+}
+
+fn run_isolated(solution: &'static Solution, cache: &Cache, force: bool, start: Instant) -> Run {
+    let started = start.elapsed();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let input = crate::read_input_file(&solution.input_file());
+        solve(solution, &input, cache, force)
+    }));
+
+    let result = match result {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+    };
+
+    Run {
+        solution,
+        result,
+        started,
+        finished: start.elapsed(),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+// Wall time of every day, from the first of its parts starting to the last one finishing
+pub fn wall_time_per_day(runs: &[Run]) -> Vec<(u32, Duration)> {
+    let mut days: Vec<(u32, Duration, Duration)> = Vec::new();
+
+    for run in runs {
+        match days.iter_mut().find(|(day, _, _)| *day == run.solution.day) {
+            Some((_, started, finished)) => {
+                *started = (*started).min(run.started);
+                *finished = (*finished).max(run.finished);
+            }
+            None => days.push((run.solution.day, run.started, run.finished)),
+        }
+    }
+
+    days.into_iter()
+        .map(|(day, started, finished)| (day, finished - started))
+        .collect()
+}
+
+// An entry is stale when it was computed by another version of the crate, for a part that does not
+// exist anymore or for an input that is not the current one
+pub fn is_stale(key: &CacheKey) -> bool {
+    if key.version != VERSION {
+        return true;
+    }
+
+    let solution = SOLUTIONS
+        .iter()
+        .find(|solution| solution.day == key.day && solution.part == key.part);
+
+    match solution {
+        Some(solution) => match fs::read_to_string(format!("input/{}", solution.input_file())) {
+            Ok(input) => CacheKey::new(key.day, key.part, &parse_input(&input)) != *key,
+            Err(_) => true,
+        },
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn empty_cache() -> Cache {
+        Cache::load(Path::new("cache/does-not-exist.txt"))
+    }
+
+    #[test]
+    fn every_solution_knows_its_input_and_output_files() {
+        let solution = &SOLUTIONS[1];
+
+        assert_eq!(solution.input_file(), "input01.txt");
+        assert_eq!(solution.output_file(), "output01b.txt");
+    }
+
+    #[test]
+    fn every_day_with_solutions_has_a_title() {
+        for solution in SOLUTIONS.iter() {
+            assert!(day_title(solution.day).starts_with(&format!("Day {}: ", solution.day)));
+        }
+    }
+
+    #[test]
+    fn the_solutions_of_a_day_can_be_obtained() {
+        let solutions = solutions_of_day(3);
+
+        assert_eq!(solutions.iter().map(|s| s.part).collect::<Vec<u32>>(), vec![1, 2]);
+        assert!(solutions_of_day(26).is_empty());
+    }
+
+    #[test]
+    fn a_stored_answer_is_reused() {
+        let mut cache = empty_cache();
+        let input = vec![String::from("1abc2")];
+
+        let answer = solve(&SOLUTIONS[0], &input, &cache, false).unwrap();
+        assert_eq!(answer.answer, "12");
+        assert!(!answer.cached);
+
+        store(&mut cache, &answer);
+
+        let answer = solve(&SOLUTIONS[0], &input, &cache, false).unwrap();
+        assert_eq!(answer.answer, "12");
+        assert!(answer.cached);
+    }
+
+    #[test]
+    fn a_forced_part_is_always_solved() {
+        let mut cache = empty_cache();
+        let input = vec![String::from("1abc2")];
+
+        let answer = solve(&SOLUTIONS[0], &input, &cache, false).unwrap();
+        store(&mut cache, &answer);
+        let answer = solve(&SOLUTIONS[0], &input, &cache, true).unwrap();
+
+        assert!(!answer.cached);
+    }
+
+    #[test]
+    fn a_different_input_is_not_answered_from_the_cache() {
+        let mut cache = empty_cache();
+
+        let answer = solve(&SOLUTIONS[0], &[String::from("1abc2")], &cache, false).unwrap();
+        store(&mut cache, &answer);
+        let answer = solve(&SOLUTIONS[0], &[String::from("3abc4")], &cache, false).unwrap();
+
+        assert_eq!(answer.answer, "34");
+        assert!(!answer.cached);
+    }
+
+    // Both are parts of day one so they can read an existing input
+    static PANICKING: Solution = Solution {
+        day: 1,
+        part: 98,
+        solve: |_| panic!("boom"),
+    };
+
+    static FAILING: Solution = Solution {
+        day: 1,
+        part: 99,
+        solve: |_| Err("Cannot solve"),
+    };
+
+    #[test]
+    fn all_parts_run_in_parallel_and_are_returned_in_order() {
+        let solutions = SOLUTIONS.iter().collect::<Vec<&Solution>>();
+
+        let runs = run_parallel(&solutions, &empty_cache(), false, 4);
+
+        let order = runs.iter().map(|run| (run.solution.day, run.solution.part)).collect::<Vec<(u32, u32)>>();
+        assert_eq!(order, vec![(1, 1), (1, 2), (2, 1), (2, 2), (3, 1), (3, 2), (4, 1), (4, 2)]);
+        assert!(runs.iter().all(|run| run.result.is_ok()));
+        assert!(runs.iter().all(|run| run.started <= run.finished));
+    }
+
+    #[test]
+    fn a_failing_or_panicking_part_does_not_abort_the_rest() {
+        let solutions = vec![&PANICKING, &SOLUTIONS[0], &FAILING];
+
+        let runs = run_parallel(&solutions, &empty_cache(), false, 2);
+
+        assert_eq!(runs[0].result, Err(String::from("panicked: boom")));
+        assert!(runs[1].result.is_ok());
+        assert_eq!(runs[2].result, Err(String::from("Cannot solve")));
+    }
+
+    #[test]
+    fn the_wall_time_of_every_day_can_be_obtained() {
+        let run = |solution, started, finished| Run {
+            solution,
+            result: Err(String::new()),
+            started: Duration::from_millis(started),
+            finished: Duration::from_millis(finished),
+        };
+
+        let runs = vec![
+            run(&SOLUTIONS[0], 0, 10),
+            run(&SOLUTIONS[1], 5, 30),
+            run(&SOLUTIONS[2], 3, 4),
+        ];
+
+        assert_eq!(
+            wall_time_per_day(&runs),
+            vec![(1, Duration::from_millis(30)), (2, Duration::from_millis(1))]
+        );
+    }
+
+    #[test]
+    fn entries_of_other_versions_or_inputs_are_stale() {
+        let input = crate::read_input_file("input01.txt");
+
+        assert!(!is_stale(&CacheKey::new(1, 1, &input)));
+        assert!(is_stale(&CacheKey::new(1, 1, &[String::from("1abc2")])));
+        assert!(is_stale(&CacheKey::new(26, 1, &input)));
+
+        let mut key = CacheKey::new(1, 1, &input);
+        key.version = String::from("0.0.0");
+        assert!(is_stale(&key));
+    }
+}